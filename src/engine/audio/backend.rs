@@ -0,0 +1,59 @@
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+use super::cue::Cue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MusicCommand {
+    // crossfades from whatever is playing into the file at this path, even if it's the same one
+    Play(String),
+    Volume(f32),
+    Speed(f32),
+}
+
+pub trait AudioBackend: Send + Sync {
+    fn play(&self, cue: Cue, volume: f32);
+    fn music(&self, command: MusicCommand);
+    // true once per track when it is close enough to its end to start fading into the next
+    fn take_track_ending(&self) -> bool;
+}
+
+// what plays when there is no audio device, nothing at all
+pub struct SilentBackend;
+
+impl AudioBackend for SilentBackend {
+    fn play(&self, _cue: Cue, _volume: f32) {}
+
+    fn music(&self, _command: MusicCommand) {}
+
+    fn take_track_ending(&self) -> bool {
+        false
+    }
+}
+
+// plays nothing, just remembers what it was asked to play
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordingBackend {
+    pub played: Mutex<Vec<Cue>>,
+    pub music: Mutex<Vec<MusicCommand>>,
+    pub track_ending: AtomicBool,
+}
+
+#[cfg(test)]
+impl AudioBackend for RecordingBackend {
+    fn play(&self, cue: Cue, _volume: f32) {
+        self.played.lock().unwrap().push(cue);
+    }
+
+    fn music(&self, command: MusicCommand) {
+        self.music.lock().unwrap().push(command);
+    }
+
+    fn take_track_ending(&self) -> bool {
+        self.track_ending.swap(false, Ordering::Relaxed)
+    }
+}
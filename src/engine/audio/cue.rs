@@ -0,0 +1,52 @@
+use crate::engine::events::types::gameplay::Gameplay;
+
+// a sound effect, played through whichever backend is active
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cue {
+    Move,
+    Rotate,
+    HardDrop,
+    Lock,
+    Hold,
+    // how many lines went at once, each count has its own sound
+    LineClear(i32),
+    LevelUp,
+    GameOver,
+}
+
+impl Cue {
+    pub fn from_gameplay(event: &Gameplay) -> Option<Self> {
+        match event {
+            // soft dropping would tick on every row, only sideways moves make a sound
+            Gameplay::PieceMoved { dx, .. } if *dx != 0 => Some(Cue::Move),
+            Gameplay::PieceMoved { .. } => None,
+            Gameplay::PieceSpawned { .. } => None,
+            Gameplay::PieceRotated { .. } => Some(Cue::Rotate),
+            Gameplay::HardDropped { .. } => Some(Cue::HardDrop),
+            Gameplay::PieceLocked { .. } => Some(Cue::Lock),
+            Gameplay::Held => Some(Cue::Hold),
+            Gameplay::LinesCleared { count, .. } if *count > 0 => Some(Cue::LineClear((*count).min(4))),
+            Gameplay::LinesCleared { .. } => None,
+            Gameplay::LevelUp { .. } => Some(Cue::LevelUp),
+            Gameplay::TopOut | Gameplay::OutOfPieces => Some(Cue::GameOver),
+        }
+    }
+
+    // the notes the cue is made of as (frequency in hz, length in ms), there are no sound files to ship
+    #[cfg(feature = "audio")]
+    pub fn tones(&self) -> Vec<(f32, u64)> {
+        match self {
+            Cue::Move => vec![(660.0, 15)],
+            Cue::Rotate => vec![(880.0, 20)],
+            Cue::HardDrop => vec![(220.0, 30), (110.0, 40)],
+            Cue::Lock => vec![(330.0, 25)],
+            Cue::Hold => vec![(523.0, 30), (784.0, 30)],
+            Cue::LineClear(1) => vec![(523.0, 60)],
+            Cue::LineClear(2) => vec![(523.0, 50), (659.0, 60)],
+            Cue::LineClear(3) => vec![(523.0, 45), (659.0, 45), (784.0, 60)],
+            Cue::LineClear(_) => vec![(523.0, 45), (659.0, 45), (784.0, 45), (1047.0, 120)],
+            Cue::LevelUp => vec![(784.0, 60), (988.0, 60), (1175.0, 100)],
+            Cue::GameOver => vec![(392.0, 150), (330.0, 150), (262.0, 300)],
+        }
+    }
+}
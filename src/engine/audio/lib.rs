@@ -0,0 +1,67 @@
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+use crate::engine::{events::types::gameplay::Gameplay, managers::game_manager::read_game_manager};
+
+use super::{
+    backend::{AudioBackend, SilentBackend},
+    cue::Cue,
+    music,
+};
+
+// silent until init finds a device
+static AUDIO_BACKEND: Lazy<RwLock<Box<dyn AudioBackend>>> =
+    Lazy::new(|| RwLock::new(Box::new(SilentBackend)));
+
+// without the audio feature, or without a device, the game stays silent
+pub fn init() {
+    #[cfg(feature = "audio")]
+    if let Ok(backend) = super::rodio_backend::RodioBackend::new() {
+        set_backend(Box::new(backend));
+    }
+    music::load_playlist();
+}
+
+pub fn set_backend(backend: Box<dyn AudioBackend>) {
+    *AUDIO_BACKEND.write().unwrap() = backend;
+}
+
+pub fn with_backend<T>(f: impl FnOnce(&dyn AudioBackend) -> T) -> T {
+    f(AUDIO_BACKEND.read().unwrap().as_ref())
+}
+
+pub fn on_gameplay(event: Gameplay) {
+    let volume = read_game_manager().save_data.settings.effects_volume;
+    with_backend(|backend| play_gameplay(backend, &event, volume));
+}
+
+// nothing is sent to the backend while effects are muted
+fn play_on(backend: &dyn AudioBackend, cue: Cue, volume: f32) {
+    if volume > 0.0 {
+        backend.play(cue, volume);
+    }
+}
+
+fn play_gameplay(backend: &dyn AudioBackend, event: &Gameplay, volume: f32) {
+    if let Some(cue) = Cue::from_gameplay(event) {
+        play_on(backend, cue, volume);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::audio::backend::RecordingBackend;
+
+    #[test]
+    fn it_does_play_a_cue_for_each_line_count() {
+        let backend = RecordingBackend::default();
+
+        play_gameplay(&backend, &Gameplay::LinesCleared { count: 2, spin: false, combo: 0 }, 0.3);
+        play_gameplay(&backend, &Gameplay::LinesCleared { count: 0, spin: false, combo: -1 }, 0.3);
+        play_gameplay(&backend, &Gameplay::LinesCleared { count: 4, spin: false, combo: 1 }, 0.3);
+        play_gameplay(&backend, &Gameplay::LinesCleared { count: 1, spin: false, combo: 2 }, 0.0);
+
+        assert_eq!(*backend.played.lock().unwrap(), vec![Cue::LineClear(2), Cue::LineClear(4)]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod backend;
+pub mod cue;
+pub mod lib;
+pub mod music;
+#[cfg(feature = "audio")]
+pub mod rodio_backend;
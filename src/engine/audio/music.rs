@@ -0,0 +1,137 @@
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+use crate::engine::{
+    common::storage,
+    managers::{
+        game_manager::{read_game_manager, write_game_manager_notice, MusicIntensity},
+        game_state::read_game_state,
+    },
+};
+
+use super::{backend::MusicCommand, lib::with_backend};
+
+// tracks are dropped in here, in the app data directory, and play in file name order
+pub const MUSIC_DIR: &str = "music";
+const MUSIC_EXTENSIONS: [&str; 3] = ["ogg", "wav", "flac"];
+
+// the music drops to this share of its volume while the game is paused
+const DUCKED_VOLUME: f32 = 0.3;
+// how many levels are played on each track when the track changes with the level
+const LEVELS_PER_TRACK: i32 = 5;
+const TEMPO_PER_LEVEL: f32 = 0.02;
+const MAX_TEMPO: f32 = 1.3;
+
+#[derive(Default)]
+struct MusicState {
+    tracks: Vec<String>,
+    current: Option<usize>,
+    volume: Option<f32>,
+    speed: Option<f32>,
+}
+
+static MUSIC_STATE: Lazy<Mutex<MusicState>> = Lazy::new(|| Mutex::new(MusicState::default()));
+
+pub fn is_track(file: &str) -> bool {
+    MUSIC_EXTENSIONS
+        .iter()
+        .any(|extension| file.to_lowercase().ends_with(&format!(".{}", extension)))
+}
+
+// reads the playlist again, keeping the current track playing if it is still there
+pub fn load_playlist() {
+    let mut tracks: Vec<String> = match storage::lib::list(MUSIC_DIR) {
+        Ok(files) => files.into_iter().filter(|file| is_track(file)).collect(),
+        Err(e) => {
+            write_game_manager_notice(Some(format!("The music folder could not be read because {}.", e)));
+            vec![]
+        }
+    };
+    tracks.sort();
+
+    let mut music = MUSIC_STATE.lock().unwrap();
+    let playing = music.current.map(|current| music.tracks[current].clone());
+    music.current = playing.and_then(|playing| tracks.iter().position(|track| *track == playing));
+    music.tracks = tracks;
+}
+
+pub fn track_count() -> usize {
+    MUSIC_STATE.lock().unwrap().tracks.len()
+}
+
+// which track of the playlist goes with the level
+pub fn level_track(level: i32, tracks: usize) -> usize {
+    ((level.max(1) - 1) / LEVELS_PER_TRACK) as usize % tracks
+}
+
+pub fn level_tempo(level: i32) -> f32 {
+    (1.0 + (level.max(1) - 1) as f32 * TEMPO_PER_LEVEL).min(MAX_TEMPO)
+}
+
+pub fn on_update() {
+    let (settings, in_game, paused) = {
+        let game_manager = read_game_manager();
+        (
+            game_manager.save_data.settings.clone(),
+            game_manager.in_game,
+            game_manager.in_game && !game_manager.running,
+        )
+    };
+    let level = if in_game {
+        read_game_state().game_data.level
+    } else {
+        1
+    };
+
+    let mut music = MUSIC_STATE.lock().unwrap();
+    if music.tracks.is_empty() {
+        return;
+    }
+
+    let volume = settings.music_volume * if paused { DUCKED_VOLUME } else { 1.0 };
+    if music.volume != Some(volume) {
+        music.volume = Some(volume);
+        with_backend(|backend| backend.music(MusicCommand::Volume(volume)));
+    }
+
+    let speed = match settings.music_intensity {
+        MusicIntensity::Tempo if in_game => level_tempo(level),
+        _ => 1.0,
+    };
+    if music.speed != Some(speed) {
+        music.speed = Some(speed);
+        with_backend(|backend| backend.music(MusicCommand::Speed(speed)));
+    }
+
+    let ending = with_backend(|backend| backend.take_track_ending());
+    let wanted = match (settings.music_intensity, music.current) {
+        (MusicIntensity::Track, _) if in_game => level_track(level, music.tracks.len()),
+        (_, Some(current)) if ending => (current + 1) % music.tracks.len(),
+        (_, Some(current)) => current,
+        (_, None) => 0,
+    };
+    // a track that runs out on its own is started again so the music keeps looping
+    if music.current != Some(wanted) || ending {
+        music.current = Some(wanted);
+        // the path only fails without a data directory, which listing the tracks already reported
+        if let Ok(path) = storage::lib::full_path(&format!("{}/{}", MUSIC_DIR, music.tracks[wanted])) {
+            with_backend(|backend| backend.music(MusicCommand::Play(path)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_follow_the_level() {
+        assert_eq!(level_track(1, 3), 0);
+        assert_eq!(level_track(6, 3), 1);
+        assert_eq!(level_track(16, 3), 0);
+        assert_eq!(level_tempo(1), 1.0);
+        assert_eq!(level_tempo(40), MAX_TEMPO);
+        assert!(is_track("01 Calm.OGG"));
+        assert!(!is_track("cover.png"));
+    }
+}
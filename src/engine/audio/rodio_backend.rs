@@ -0,0 +1,211 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rodio::{source, Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+
+use super::{
+    backend::{AudioBackend, MusicCommand},
+    cue::Cue,
+};
+
+// how long one track takes to fade into the next
+const CROSSFADE: Duration = Duration::from_secs(2);
+// how often the audio thread updates fades when it has nothing else to do
+const FADE_STEP: Duration = Duration::from_millis(20);
+// how much the music volume can move per second, so ducking eases in and out
+const VOLUME_RATE: f32 = 1.5;
+
+enum Command {
+    Cue(Cue, f32),
+    Music(MusicCommand),
+}
+
+pub struct RodioBackend {
+    sender: Mutex<mpsc::Sender<Command>>,
+    track_ending: Arc<AtomicBool>,
+}
+
+impl RodioBackend {
+    // the output stream can't leave the thread that opened it, so it lives on its own thread
+    // and commands are sent over to it, fails if there is no device to play on
+    pub fn new() -> Result<Self, String> {
+        let (sender, receiver) = mpsc::channel::<Command>();
+        let (ready, opened) = mpsc::channel();
+        let track_ending = Arc::new(AtomicBool::new(false));
+        let ending = track_ending.clone();
+
+        thread::spawn(move || {
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(stream) => {
+                    let _ = ready.send(Ok(()));
+                    stream
+                }
+                Err(e) => {
+                    let _ = ready.send(Err(e.to_string()));
+                    return;
+                }
+            };
+            let mut music = MusicPlayer::new(handle.clone(), ending);
+            loop {
+                match receiver.recv_timeout(FADE_STEP) {
+                    Ok(Command::Cue(cue, volume)) => {
+                        let notes = cue.tones().into_iter().map(|(frequency, length)| {
+                            source::SineWave::new(frequency)
+                                .take_duration(Duration::from_millis(length))
+                        });
+                        // a cue that can't be played is just not heard
+                        let _ = handle.play_raw(source::from_iter(notes).amplify(volume));
+                    }
+                    Ok(Command::Music(command)) => music.handle(command),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                music.step();
+            }
+        });
+
+        match opened.recv() {
+            Ok(Ok(())) => Ok(Self {
+                sender: Mutex::new(sender),
+                track_ending,
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err("the audio thread stopped".to_string()),
+        }
+    }
+}
+
+impl AudioBackend for RodioBackend {
+    fn play(&self, cue: Cue, volume: f32) {
+        let _ = self.sender.lock().unwrap().send(Command::Cue(cue, volume));
+    }
+
+    fn music(&self, command: MusicCommand) {
+        let _ = self.sender.lock().unwrap().send(Command::Music(command));
+    }
+
+    fn take_track_ending(&self) -> bool {
+        self.track_ending.swap(false, Ordering::Relaxed)
+    }
+}
+
+struct Track {
+    sink: Sink,
+    started: Instant,
+    length: Option<Duration>,
+    ending_sent: bool,
+}
+
+// lives on the audio thread, fades tracks in and out as they change
+struct MusicPlayer {
+    handle: OutputStreamHandle,
+    current: Option<Track>,
+    // tracks fading out, with the volume they were at when the fade started
+    fading: Vec<(Track, f32)>,
+    volume: f32,
+    target_volume: f32,
+    speed: f32,
+    last_step: Instant,
+    track_ending: Arc<AtomicBool>,
+}
+
+impl MusicPlayer {
+    fn new(handle: OutputStreamHandle, track_ending: Arc<AtomicBool>) -> Self {
+        Self {
+            handle,
+            current: None,
+            fading: vec![],
+            volume: 0.0,
+            target_volume: 0.0,
+            speed: 1.0,
+            last_step: Instant::now(),
+            track_ending,
+        }
+    }
+
+    fn handle(&mut self, command: MusicCommand) {
+        match command {
+            MusicCommand::Play(path) => {
+                // a track that can't be opened is skipped, the current one keeps playing
+                let Ok((sink, length)) = open_track(&self.handle, &path) else {
+                    return;
+                };
+                sink.set_speed(self.speed);
+                if let Some(track) = self.current.take() {
+                    let volume = track.sink.volume();
+                    self.fading.push((
+                        Track {
+                            started: Instant::now(),
+                            ..track
+                        },
+                        volume,
+                    ));
+                }
+                self.current = Some(Track {
+                    sink,
+                    started: Instant::now(),
+                    length,
+                    ending_sent: false,
+                });
+            }
+            MusicCommand::Volume(volume) => self.target_volume = volume.clamp(0.0, 1.0),
+            MusicCommand::Speed(speed) => {
+                self.speed = speed;
+                if let Some(track) = &self.current {
+                    track.sink.set_speed(speed);
+                }
+            }
+        }
+    }
+
+    fn step(&mut self) {
+        let elapsed = self.last_step.elapsed().as_secs_f32();
+        self.last_step = Instant::now();
+        let change = (self.target_volume - self.volume).clamp(-VOLUME_RATE * elapsed, VOLUME_RATE * elapsed);
+        self.volume += change;
+
+        if let Some(track) = &mut self.current {
+            let fade_in = (track.started.elapsed().as_secs_f32() / CROSSFADE.as_secs_f32()).min(1.0);
+            track.sink.set_volume(self.volume * fade_in);
+
+            let near_end = match track.length {
+                Some(length) => track.sink.get_pos() + CROSSFADE >= length,
+                None => false,
+            };
+            if !track.ending_sent && (near_end || track.sink.empty()) {
+                track.ending_sent = true;
+                self.track_ending.store(true, Ordering::Relaxed);
+            }
+        }
+
+        self.fading.retain(|(track, from)| {
+            let left = 1.0 - track.started.elapsed().as_secs_f32() / CROSSFADE.as_secs_f32();
+            if left <= 0.0 {
+                track.sink.stop();
+                return false;
+            }
+            track.sink.set_volume(from * left);
+            true
+        });
+    }
+}
+
+// not every format knows its length up front, those just signal when they run out
+fn open_track(handle: &OutputStreamHandle, path: &str) -> Result<(Sink, Option<Duration>), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let decoder = Decoder::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+    let length = decoder.total_duration();
+    let sink = Sink::try_new(handle).map_err(|e| e.to_string())?;
+    sink.set_volume(0.0);
+    sink.append(decoder);
+    Ok((sink, length))
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use crate::engine::common::{
+    export::{default_export_dir, export, ExportFormat},
+    storage::{
+        profile,
+        save_file::{self, LoadOutcome},
+    },
+};
+
+const USAGE: &str = "usage: revris --export <csv|json> [--export-dir <dir>] [--profile <name>]";
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<Option<&'a String>> {
+    args.iter().position(|arg| arg == flag).map(|at| args.get(at + 1))
+}
+
+// handles command line flags that run without opening the game,
+// returns the exit code when one was handled
+pub fn run(args: &[String]) -> Option<i32> {
+    let export_at = args.iter().position(|arg| arg == "--export")?;
+    let Some(format) = args.get(export_at + 1).and_then(|name| ExportFormat::parse(name)) else {
+        eprintln!("{}", USAGE);
+        return Some(2);
+    };
+    let dir = match flag_value(args, "--export-dir") {
+        Some(Some(dir)) => PathBuf::from(dir),
+        Some(None) => {
+            eprintln!("{}", USAGE);
+            return Some(2);
+        }
+        None => match default_export_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                eprintln!("Could not find a directory to export to: {}", e);
+                return Some(1);
+            }
+        },
+    };
+
+    // the named profile, or whoever played last
+    let index = match profile::load_index() {
+        Ok(index) => index,
+        Err(e) => {
+            eprintln!("The profile list could not be read: {}", e);
+            return Some(1);
+        }
+    };
+    let chosen = match flag_value(args, "--profile") {
+        Some(Some(name)) => index
+            .profiles
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name) || &p.id == name)
+            .map(|p| p.id.clone()),
+        Some(None) => {
+            eprintln!("{}", USAGE);
+            return Some(2);
+        }
+        None => index.last_used.clone(),
+    };
+    let Some(id) = chosen else {
+        eprintln!("No matching profile, pick one with --profile <name>");
+        return Some(1);
+    };
+    profile::set_active(Some(id));
+
+    // loading also moves history out of older saves, so the export sees all of it
+    let save_data = match save_file::load() {
+        LoadOutcome::New(save_data) | LoadOutcome::Loaded(save_data) => save_data,
+        LoadOutcome::Recovered(save_data, notice) => {
+            eprintln!("{}", notice);
+            save_data
+        }
+        LoadOutcome::Unavailable(_, notice) => {
+            eprintln!("{}", notice);
+            return Some(1);
+        }
+    };
+
+    match export(format, &dir, &save_data.totals) {
+        Ok(path) => {
+            println!("Exported history to {}", path.to_string_lossy());
+            Some(0)
+        }
+        Err(e) => {
+            eprintln!("Export failed: {}", e);
+            Some(1)
+        }
+    }
+}
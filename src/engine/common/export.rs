@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use platform_dirs::UserDirs;
+use serde::Serialize;
+
+use crate::engine::{
+    common::storage::{
+        error::StorageError,
+        history::{self, HistoryQuery},
+        lib::full_path,
+    },
+    managers::{
+        game_manager::{GameData, HistoryTotals},
+        game_statics::read_game_statics,
+    },
+};
+
+// bumped if a column is ever renamed or removed, new columns only get appended
+const EXPORT_VERSION: u32 = 1;
+
+// column names in the order they are written, these are relied on by spreadsheets
+const COLUMNS: [&str; 15] = [
+    "id",
+    "mode",
+    "score",
+    "level",
+    "lines_cleared",
+    "pieces_placed",
+    "garbage_cleared",
+    "ticks_played",
+    "seconds_played",
+    "pieces_per_second",
+    "lines_per_minute",
+    "score_per_piece",
+    "seed",
+    "start_time",
+    "end_time",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<ExportFormat> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct ExportedGame {
+    id: i32,
+    mode: String,
+    score: i32,
+    level: i32,
+    lines_cleared: i32,
+    pieces_placed: i32,
+    garbage_cleared: i32,
+    ticks_played: i32,
+    seconds_played: f64,
+    pieces_per_second: f64,
+    lines_per_minute: f64,
+    score_per_piece: f64,
+    seed: u64,
+    start_time: String,
+    end_time: String,
+}
+
+#[derive(Serialize)]
+struct Export {
+    version: u32,
+    exported_at: String,
+    totals: HistoryTotals,
+    games: Vec<ExportedGame>,
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// games that never ran a tick get 0 rather than a division by zero
+fn rate(amount: f64, per: f64) -> f64 {
+    if per > 0.0 {
+        amount / per
+    } else {
+        0.0
+    }
+}
+
+fn exported_game(game: &GameData, tick_rate: i32) -> ExportedGame {
+    let seconds_played = game.ticks_played as f64 / tick_rate as f64;
+    ExportedGame {
+        id: game.id,
+        mode: game.mode.name().to_string(),
+        score: game.score,
+        level: game.level,
+        lines_cleared: game.lines_cleared,
+        pieces_placed: game.pieces_placed,
+        garbage_cleared: game.garbage_cleared,
+        ticks_played: game.ticks_played,
+        seconds_played,
+        pieces_per_second: rate(game.pieces_placed as f64, seconds_played),
+        lines_per_minute: rate(game.lines_cleared as f64, seconds_played / 60.0),
+        score_per_piece: rate(game.score as f64, game.pieces_placed as f64),
+        seed: game.seed,
+        start_time: timestamp(&game.start_time),
+        end_time: timestamp(&game.end_time),
+    }
+}
+
+fn csv_field(value: String) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn to_csv(games: &[ExportedGame]) -> String {
+    let mut csv = COLUMNS.join(",") + "\n";
+    for game in games {
+        let fields = [
+            game.id.to_string(),
+            game.mode.clone(),
+            game.score.to_string(),
+            game.level.to_string(),
+            game.lines_cleared.to_string(),
+            game.pieces_placed.to_string(),
+            game.garbage_cleared.to_string(),
+            game.ticks_played.to_string(),
+            format!("{:.2}", game.seconds_played),
+            format!("{:.3}", game.pieces_per_second),
+            format!("{:.3}", game.lines_per_minute),
+            format!("{:.1}", game.score_per_piece),
+            game.seed.to_string(),
+            game.start_time.clone(),
+            game.end_time.clone(),
+        ];
+        csv.push_str(&fields.map(csv_field).join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// the documents folder, or an exports folder next to the save when there isn't one
+pub fn default_export_dir() -> Result<PathBuf, StorageError> {
+    match UserDirs::new() {
+        Some(user_dirs) => Ok(user_dirs.document_dir),
+        None => Ok(PathBuf::from(full_path("exports")?)),
+    }
+}
+
+// writes every game in the history to a new file in `dir` and returns its path
+pub fn export(format: ExportFormat, dir: &Path, totals: &HistoryTotals) -> Result<PathBuf, StorageError> {
+    let tick_rate = read_game_statics().tick_rate;
+    let mut games: Vec<ExportedGame> = history::query(HistoryQuery::default())?
+        .map(|game| exported_game(&game, tick_rate))
+        .collect();
+    games.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+
+    let data = match format {
+        ExportFormat::Csv => to_csv(&games),
+        ExportFormat::Json => serde_json::to_string_pretty(&Export {
+            version: EXPORT_VERSION,
+            exported_at: timestamp(&Utc::now()),
+            totals: totals.clone(),
+            games,
+        })
+        .map_err(|e| StorageError::Corrupt(e.to_string()))?,
+    };
+
+    let dir_name = dir.to_string_lossy().to_string();
+    std::fs::create_dir_all(dir).map_err(|e| StorageError::from_io(&dir_name, e))?;
+    let path = dir.join(format!(
+        "revris-history-{}.{}",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        format.extension()
+    ));
+    std::fs::write(&path, data).map_err(|e| StorageError::from_io(&path.to_string_lossy(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::managers::game_manager::GameMode;
+
+    #[test]
+    fn it_does_write_stable_csv_columns() {
+        let mut game = GameData::new();
+        game.id = 7;
+        game.mode = GameMode::Dig;
+        game.pieces_placed = 30;
+        game.ticks_played = 600;
+        game.start_time = "2024-10-01T10:00:00Z".parse().unwrap();
+        let csv = to_csv(&[exported_game(&game, 60)]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], COLUMNS.join(","));
+        assert!(lines[1].starts_with("7,Dig,0,1,0,30,0,600,10.00,3.000,0.000,0.0,0,2024-10-01T10:00:00Z,"));
+        assert_eq!(csv_field("a,b".to_string()), "\"a,b\"");
+    }
+}
@@ -0,0 +1,101 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::engine::managers::game_state::GARBAGE_CELL;
+
+#[derive(Debug, Clone)]
+pub struct GarbageGenerator {
+    pub seed: u64,
+    // chance from 0.0 to 1.0 that the hole moves to a new column on the next row
+    pub messiness: f32,
+    rng: StdRng,
+    hole: Option<usize>,
+}
+
+impl GarbageGenerator {
+    pub fn new(seed: u64, messiness: f32) -> Self {
+        Self {
+            seed,
+            messiness: messiness.clamp(0.0, 1.0),
+            rng: StdRng::seed_from_u64(seed),
+            hole: None,
+        }
+    }
+
+    pub fn next_row(&mut self, width: usize) -> Vec<i32> {
+        let hole = match self.hole {
+            Some(hole) if hole < width && !self.rng.gen_bool(self.messiness as f64) => hole,
+            _ => self.rng.gen_range(0..width),
+        };
+        self.hole = Some(hole);
+
+        let mut row = vec![GARBAGE_CELL; width];
+        row[hole] = 0;
+        row
+    }
+}
+
+impl Default for GarbageGenerator {
+    fn default() -> Self {
+        Self::new(0, 0.0)
+    }
+}
+
+// shifts everything in the arena up by `rows` and fills the bottom with garbage,
+// returns false if a non empty cell was pushed out of the top
+pub fn push_garbage_rows(arena: &mut Vec<Vec<i32>>, generator: &mut GarbageGenerator, rows: usize) -> bool {
+    let mut fits = true;
+    let width = arena[0].len();
+    for _ in 0..rows {
+        let removed = arena.remove(0);
+        if removed.iter().any(|&cell| cell != 0) {
+            fits = false;
+        }
+        arena.push(generator.next_row(width));
+    }
+    fits
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_leave_one_hole_per_row() {
+        let mut generator = GarbageGenerator::new(42, 0.5);
+        for _ in 0..50 {
+            let row = generator.next_row(10);
+            assert_eq!(row.iter().filter(|&&cell| cell == 0).count(), 1);
+            assert_eq!(row.iter().filter(|&&cell| cell == GARBAGE_CELL).count(), 9);
+        }
+    }
+
+    #[test]
+    fn it_does_repeat_with_the_same_seed() {
+        let mut first = GarbageGenerator::new(7, 0.3);
+        let mut second = GarbageGenerator::new(7, 0.3);
+        for _ in 0..20 {
+            assert_eq!(first.next_row(10), second.next_row(10));
+        }
+    }
+
+    #[test]
+    fn it_does_keep_the_hole_when_clean() {
+        let mut generator = GarbageGenerator::new(3, 0.0);
+        let first = generator.next_row(10);
+        for _ in 0..20 {
+            assert_eq!(generator.next_row(10), first);
+        }
+    }
+
+    #[test]
+    fn it_does_push_rows_up() {
+        let mut arena = vec![vec![0; 10]; 5];
+        arena[4][0] = 5;
+        let mut generator = GarbageGenerator::new(1, 0.0);
+
+        assert!(push_garbage_rows(&mut arena, &mut generator, 2));
+        assert_eq!(arena.len(), 5);
+        assert_eq!(arena[2][0], 5);
+        assert!(arena[4].contains(&GARBAGE_CELL));
+    }
+}
@@ -0,0 +1,133 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::managers::game_manager::{GameData, GameMode};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    // the game in the history this entry came from
+    pub game_id: i32,
+    pub mode: GameMode,
+    pub score: i32,
+    pub ticks: i32,
+    pub lines_cleared: i32,
+    pub date: DateTime<Utc>,
+}
+
+impl LeaderboardEntry {
+    pub fn from_game(name: String, game: &GameData) -> Self {
+        Self {
+            name,
+            game_id: game.id,
+            mode: game.mode,
+            score: game.score,
+            ticks: game.ticks_played,
+            lines_cleared: game.lines_cleared,
+            date: game.end_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ranking {
+    HighestScore,
+    FastestTime,
+    LongestTime,
+}
+
+impl Ranking {
+    // modes without a ranking don't get a leaderboard
+    pub fn of(mode: GameMode) -> Option<Ranking> {
+        match mode {
+            GameMode::Marathon | GameMode::Zen => Some(Ranking::HighestScore),
+            GameMode::Dig => Some(Ranking::FastestTime),
+            GameMode::Survival => Some(Ranking::LongestTime),
+            GameMode::Puzzle => None,
+        }
+    }
+
+    // better entries sort first, ties go to whoever got there first
+    pub fn compare(&self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        let ordering = match self {
+            Ranking::HighestScore => b.score.cmp(&a.score),
+            Ranking::FastestTime => a.ticks.cmp(&b.ticks),
+            Ranking::LongestTime => b.ticks.cmp(&a.ticks),
+        };
+        ordering.then(a.date.cmp(&b.date))
+    }
+}
+
+// the entries for one mode, best first
+pub fn board(entries: &[LeaderboardEntry], mode: GameMode) -> Vec<LeaderboardEntry> {
+    let Some(ranking) = Ranking::of(mode) else {
+        return vec![];
+    };
+    let mut board: Vec<LeaderboardEntry> =
+        entries.iter().filter(|e| e.mode == mode).cloned().collect();
+    board.sort_by(|a, b| ranking.compare(a, b));
+    board
+}
+
+// where the entry would place on its board, if it makes the top `size`
+pub fn rank(entries: &[LeaderboardEntry], entry: &LeaderboardEntry, size: usize) -> Option<usize> {
+    let ranking = Ranking::of(entry.mode)?;
+    let position = board(entries, entry.mode)
+        .iter()
+        .filter(|e| ranking.compare(e, entry) != Ordering::Greater)
+        .count();
+    (position < size).then_some(position)
+}
+
+// adds the entry and drops whatever fell off the bottom of its board
+pub fn insert(entries: &mut Vec<LeaderboardEntry>, entry: LeaderboardEntry, size: usize) {
+    let mode = entry.mode;
+    entries.push(entry);
+    let kept = board(entries, mode).into_iter().take(size).collect::<Vec<_>>();
+    entries.retain(|e| e.mode != mode);
+    entries.extend(kept);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(mode: GameMode, score: i32, ticks: i32) -> LeaderboardEntry {
+        let mut game = GameData::new();
+        game.mode = mode;
+        game.score = score;
+        game.ticks_played = ticks;
+        LeaderboardEntry::from_game("cat".to_string(), &game)
+    }
+
+    #[test]
+    fn it_does_rank_each_mode_its_own_way() {
+        let mut entries = vec![];
+        insert(&mut entries, entry(GameMode::Marathon, 100, 0), 2);
+        insert(&mut entries, entry(GameMode::Marathon, 300, 0), 2);
+        insert(&mut entries, entry(GameMode::Dig, 0, 900), 2);
+        insert(&mut entries, entry(GameMode::Dig, 0, 600), 2);
+
+        assert_eq!(board(&entries, GameMode::Marathon)[0].score, 300);
+        assert_eq!(board(&entries, GameMode::Dig)[0].ticks, 600);
+        assert_eq!(rank(&entries, &entry(GameMode::Marathon, 200, 0), 2), Some(1));
+        assert_eq!(rank(&entries, &entry(GameMode::Dig, 0, 1000), 2), None);
+        assert_eq!(rank(&entries, &entry(GameMode::Puzzle, 1000, 0), 2), None);
+    }
+
+    #[test]
+    fn it_does_keep_only_the_top_entries() {
+        let mut entries = vec![];
+        for score in [100, 500, 300] {
+            insert(&mut entries, entry(GameMode::Survival, score, score), 2);
+        }
+        insert(&mut entries, entry(GameMode::Zen, 50, 0), 2);
+
+        let survival = board(&entries, GameMode::Survival);
+        assert_eq!(survival.len(), 2);
+        assert_eq!(survival[1].ticks, 300);
+        assert_eq!(entries.len(), 3);
+    }
+}
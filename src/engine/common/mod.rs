@@ -1,2 +1,7 @@
+pub mod export;
+pub mod garbage;
+pub mod leaderboard;
+pub mod navigation;
+pub mod puzzle;
 pub mod storage;
 pub mod ui;
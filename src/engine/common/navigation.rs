@@ -0,0 +1,93 @@
+use crate::engine::managers::game_manager::{
+    read_game_manager, read_game_manager_only, write_game_manager, write_game_manager_screen_result,
+};
+
+// what a screen hands back to the one under it when it closes, like a choice made in a dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenResult {
+    pub from: String,
+    pub value: String,
+}
+
+// opens a screen on top of the current one, going back returns to the current one
+pub fn push(screen: &str) {
+    let mut game_manager = read_game_manager_only();
+    let current = std::mem::replace(&mut game_manager.screen, screen.to_string());
+    game_manager.screen_path.push(current);
+    write_game_manager(game_manager);
+}
+
+// returns to the screen under the current one, false if there is nothing to go back to
+pub fn pop() -> bool {
+    let mut game_manager = read_game_manager_only();
+    let Some(previous) = game_manager.screen_path.pop() else {
+        return false;
+    };
+    game_manager.screen = previous;
+    write_game_manager(game_manager);
+    true
+}
+
+// goes back, leaving a result for the screen under this one to pick up with take_result
+pub fn pop_with(value: String) -> bool {
+    let from = read_game_manager().screen.clone();
+    if !pop() {
+        return false;
+    }
+    write_game_manager_screen_result(Some(ScreenResult { from, value }));
+    true
+}
+
+// swaps the current screen without changing where going back leads
+pub fn replace(screen: &str) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.screen = screen.to_string();
+    write_game_manager(game_manager);
+}
+
+// starts over from a screen with nothing to go back to, like the main menu or a game
+pub fn reset(screen: &str) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.screen = screen.to_string();
+    game_manager.screen_path.clear();
+    game_manager.screen_result = None;
+    write_game_manager(game_manager);
+}
+
+pub fn take_result() -> Option<ScreenResult> {
+    let result = read_game_manager().screen_result.clone();
+    if result.is_some() {
+        write_game_manager_screen_result(None);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::managers::lock_globals;
+
+    #[test]
+    fn it_does_go_back_with_a_result() {
+        let _globals = lock_globals();
+        reset("main");
+        push("settings");
+        push("rebind");
+        replace("rebind_confirm");
+
+        assert!(pop_with("KEY_X".to_string()));
+        assert_eq!(read_game_manager().screen, "settings");
+        assert_eq!(
+            take_result(),
+            Some(ScreenResult {
+                from: "rebind_confirm".to_string(),
+                value: "KEY_X".to_string(),
+            })
+        );
+        assert_eq!(take_result(), None);
+
+        assert!(pop());
+        assert!(!pop());
+        assert_eq!(read_game_manager().screen, "main");
+    }
+}
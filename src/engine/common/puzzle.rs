@@ -0,0 +1,223 @@
+use raylib::color::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{
+    common::storage::{self, error::StorageError},
+    managers::{
+        game_manager::Block,
+        game_state::{ARENA_HEIGHT, GARBAGE_CELL, SPAWN_ROWS},
+    },
+};
+
+pub const PUZZLE_DIR: &str = "puzzles";
+pub const PUZZLE_EXTENSION: &str = "rvpz";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Objective {
+    ClearLines(i32),
+    PerfectClear,
+    Spin,
+}
+
+impl Objective {
+    pub fn describe(&self) -> String {
+        match self {
+            Objective::ClearLines(lines) => format!("Clear {} lines", lines),
+            Objective::PerfectClear => "Perfect clear".to_string(),
+            Objective::Spin => "Clear with a spin".to_string(),
+        }
+    }
+}
+
+// a block written out in a puzzle file or a save, since Block itself can't be serialized
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PuzzleBlock {
+    pub name: String,
+    pub layout: Vec<Vec<i32>>,
+    #[serde(default)]
+    pub can_rotate: bool,
+    pub color: (u8, u8, u8, u8),
+}
+
+impl PuzzleBlock {
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            name: block.name.clone(),
+            layout: block.layout.clone(),
+            can_rotate: block.can_rotate,
+            color: (block.color.r, block.color.g, block.color.b, block.color.a),
+        }
+    }
+
+    pub fn to_block(&self) -> Block {
+        Block {
+            layout: self.layout.clone(),
+            can_rotate: self.can_rotate,
+            color: Color::new(self.color.0, self.color.1, self.color.2, self.color.3),
+            name: self.name.clone(),
+            active: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum PuzzlePiece {
+    // one of the blocks in GameManager::pieces, by name
+    Named(String),
+    Custom(PuzzleBlock),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Puzzle {
+    // file name without the extension, filled in when loading
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    // rows from the top down, lined up with the bottom of the arena,
+    // '.' or ' ' is empty and anything else is a filled cell
+    pub arena: Vec<String>,
+    pub pieces: Vec<PuzzlePiece>,
+    #[serde(default = "default_hold")]
+    pub hold: bool,
+    pub objective: Objective,
+}
+
+fn default_hold() -> bool {
+    true
+}
+
+impl Puzzle {
+    pub fn parse(id: &str, data: &str) -> Result<Self, ron::error::SpannedError> {
+        let mut puzzle: Puzzle = ron::de::from_str(data)?;
+        puzzle.id = id.to_string();
+        Ok(puzzle)
+    }
+
+    pub fn build_arena(&self, width: usize, height: usize) -> Vec<Vec<i32>> {
+        let mut arena = vec![vec![0; width]; height];
+        for (y, row) in self.arena.iter().rev().take(height).enumerate() {
+            for (x, cell) in row.chars().take(width).enumerate() {
+                if cell != '.' && cell != ' ' {
+                    arena[height - 1 - y][x] = GARBAGE_CELL;
+                }
+            }
+        }
+        arena
+    }
+
+    // a puzzle reaching into the spawn rows would top out on its first piece
+    pub fn blocks_spawn(&self) -> bool {
+        let filled = |row: &String| row.chars().any(|cell| cell != '.' && cell != ' ');
+        self.arena.iter().rev().skip(ARENA_HEIGHT - SPAWN_ROWS).any(filled)
+    }
+
+    // resolves the piece sequence, every name has to be one of `pieces`
+    pub fn build_queue(&self, pieces: &[Block]) -> Result<Vec<Block>, StorageError> {
+        let mut queue = vec![];
+        for piece in self.pieces.iter() {
+            match piece {
+                PuzzlePiece::Named(name) => match pieces.iter().find(|block| &block.name == name) {
+                    Some(block) => queue.push(block.clone()),
+                    None => {
+                        return Err(StorageError::Corrupt(format!(
+                            "it uses a piece called {} that doesn't exist",
+                            name
+                        )))
+                    }
+                },
+                PuzzlePiece::Custom(block) => queue.push(block.to_block()),
+            }
+        }
+        Ok(queue)
+    }
+}
+
+// a puzzle file that was left out of the list and why
+pub type PuzzleProblem = (String, StorageError);
+
+fn load_puzzle(file: &str, id: &str, pieces: &[Block]) -> Result<Puzzle, StorageError> {
+    let data = storage::lib::load(&format!("{}/{}", PUZZLE_DIR, file))?;
+    let puzzle = Puzzle::parse(id, &data)
+        .map_err(|e| StorageError::Corrupt(format!("it could not be read ({})", e)))?;
+    if puzzle.blocks_spawn() {
+        return Err(StorageError::Corrupt("it has cells in the spawn rows".to_string()));
+    }
+    puzzle.build_queue(pieces)?;
+    Ok(puzzle)
+}
+
+// loads every puzzle in the puzzle directory, sorted by file name, along with the files that
+// couldn't be used
+pub fn load_puzzles(pieces: &[Block]) -> Result<(Vec<Puzzle>, Vec<PuzzleProblem>), StorageError> {
+    let mut puzzles = vec![];
+    let mut problems = vec![];
+    let mut files = storage::lib::list(PUZZLE_DIR)?;
+    files.sort();
+    for file in files {
+        let Some(id) = file.strip_suffix(&format!(".{}", PUZZLE_EXTENSION)) else {
+            continue;
+        };
+        match load_puzzle(&file, id, pieces) {
+            Ok(puzzle) => puzzles.push(puzzle),
+            Err(e) => problems.push((file, e)),
+        }
+    }
+    Ok((puzzles, problems))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUZZLE: &str = r#"(
+        name: "Well",
+        arena: [
+            "XXX.",
+            "XXX.",
+        ],
+        pieces: [Named("I"), Custom((name: "Dot", layout: [[1]], color: (255, 0, 0, 255)))],
+        hold: false,
+        objective: ClearLines(2),
+    )"#;
+
+    #[test]
+    fn it_does_parse_a_puzzle() {
+        let puzzle = Puzzle::parse("well", PUZZLE).unwrap();
+        assert_eq!(puzzle.id, "well");
+        assert!(!puzzle.hold);
+        assert_eq!(puzzle.objective, Objective::ClearLines(2));
+        assert_eq!(puzzle.pieces.len(), 2);
+    }
+
+    #[test]
+    fn it_does_build_the_arena_from_the_bottom() {
+        let puzzle = Puzzle::parse("well", PUZZLE).unwrap();
+        let arena = puzzle.build_arena(4, 5);
+        assert_eq!(arena[2], vec![0, 0, 0, 0]);
+        assert_eq!(arena[3], vec![GARBAGE_CELL, GARBAGE_CELL, GARBAGE_CELL, 0]);
+        assert_eq!(arena[4], vec![GARBAGE_CELL, GARBAGE_CELL, GARBAGE_CELL, 0]);
+    }
+
+    #[test]
+    fn it_does_find_cells_in_the_spawn_rows() {
+        let mut puzzle = Puzzle::parse("well", PUZZLE).unwrap();
+        assert!(!puzzle.blocks_spawn());
+
+        puzzle.arena = vec![".".to_string(); ARENA_HEIGHT - SPAWN_ROWS];
+        puzzle.arena.insert(0, "X".to_string());
+        assert!(puzzle.blocks_spawn());
+        puzzle.arena[0] = ".".to_string();
+        assert!(!puzzle.blocks_spawn());
+    }
+
+    #[test]
+    fn it_does_reject_unknown_pieces() {
+        let mut puzzle = Puzzle::parse("well", PUZZLE).unwrap();
+        assert!(puzzle.build_queue(&[]).is_err());
+
+        puzzle.pieces.remove(0);
+        let queue = puzzle.build_queue(&[]).unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].name, "Dot");
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    // the platform didn't give us a data directory to work in
+    MissingDir,
+    NotFound(String),
+    PermissionDenied(String),
+    // the file is there but its contents can't be used
+    Corrupt(String),
+    Other(String, String),
+}
+
+impl StorageError {
+    pub fn from_io(path: &str, error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound(path.to_string()),
+            std::io::ErrorKind::PermissionDenied => StorageError::PermissionDenied(path.to_string()),
+            std::io::ErrorKind::InvalidData => {
+                StorageError::Corrupt(format!("{} is not valid text", path))
+            }
+            _ => StorageError::Other(path.to_string(), error.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::MissingDir => write!(f, "no data directory could be found"),
+            StorageError::NotFound(path) => write!(f, "{} does not exist", path),
+            StorageError::PermissionDenied(path) => write!(f, "permission to {} was denied", path),
+            StorageError::Corrupt(reason) => write!(f, "{}", reason),
+            StorageError::Other(path, error) => write!(f, "{} failed ({})", path, error),
+        }
+    }
+}
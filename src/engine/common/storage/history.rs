@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{
+    common::storage::{self, error::StorageError, profile},
+    managers::game_manager::{GameData, GameMode, HistoryTotals, SaveData},
+};
+
+// one record per line, only ever appended to outside of compaction
+pub const HISTORY_FILE: &str = "history.rvlog";
+
+// the log of whichever profile is active
+fn history_path() -> String {
+    profile::active_path(HISTORY_FILE)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum HistoryRecord {
+    Game(GameData),
+    // a deleted game, dropped along with the game itself on the next compaction
+    Deleted(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryColumn {
+    Date,
+    Mode,
+    Score,
+    Level,
+    Lines,
+    Duration,
+}
+
+impl HistoryColumn {
+    pub const ALL: [HistoryColumn; 6] = [
+        HistoryColumn::Date,
+        HistoryColumn::Mode,
+        HistoryColumn::Score,
+        HistoryColumn::Level,
+        HistoryColumn::Lines,
+        HistoryColumn::Duration,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HistoryColumn::Date => "Date",
+            HistoryColumn::Mode => "Mode",
+            HistoryColumn::Score => "Score",
+            HistoryColumn::Level => "Level",
+            HistoryColumn::Lines => "Lines",
+            HistoryColumn::Duration => "Duration",
+        }
+    }
+}
+
+pub fn sort_games(games: &mut [GameData], column: HistoryColumn, descending: bool) {
+    games.sort_by(|a, b| {
+        let ordering = match column {
+            HistoryColumn::Date => a.start_time.cmp(&b.start_time),
+            HistoryColumn::Mode => a.mode.name().cmp(b.mode.name()),
+            HistoryColumn::Score => a.score.cmp(&b.score),
+            HistoryColumn::Level => a.level.cmp(&b.level),
+            HistoryColumn::Lines => a.lines_cleared.cmp(&b.lines_cleared),
+            HistoryColumn::Duration => (a.end_time - a.start_time).cmp(&(b.end_time - b.start_time)),
+        };
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    pub mode: Option<GameMode>,
+    // games that ended from this time, inclusive
+    pub from: Option<DateTime<Utc>>,
+    // games that ended before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl HistoryQuery {
+    pub fn matches(&self, game: &GameData) -> bool {
+        self.mode.is_none_or(|mode| game.mode == mode)
+            && self.from.is_none_or(|from| game.end_time >= from)
+            && self.to.is_none_or(|to| game.end_time < to)
+    }
+}
+
+fn parse_line(line: &str) -> Option<HistoryRecord> {
+    ron::de::from_str(line).ok()
+}
+
+fn write_line(record: &HistoryRecord) -> String {
+    ron::ser::to_string(record).unwrap()
+}
+
+pub fn append(game: &GameData) -> Result<(), StorageError> {
+    storage::lib::append_line(&history_path(), &write_line(&HistoryRecord::Game(game.clone())))
+}
+
+pub fn delete(id: i32) -> Result<(), StorageError> {
+    storage::lib::append_line(&history_path(), &write_line(&HistoryRecord::Deleted(id)))
+}
+
+// the games the log has no record of, a deleted game still counts as recorded so it stays deleted
+fn unrecorded(lines: impl Iterator<Item = String>, games: &[GameData]) -> Vec<&GameData> {
+    let recorded: HashSet<i32> = lines
+        .filter_map(|line| match parse_line(&line)? {
+            HistoryRecord::Game(game) => Some(game.id),
+            HistoryRecord::Deleted(id) => Some(id),
+        })
+        .collect();
+    games.iter().filter(|game| !recorded.contains(&game.id)).collect()
+}
+
+// appends the games an older save hands over that the log doesn't have yet, never dropping any
+// it already has, since the save may be a backup from before them
+pub fn merge(games: &[GameData]) -> Result<(), StorageError> {
+    for game in unrecorded(records()?, games) {
+        append(game)?;
+    }
+    Ok(())
+}
+
+// a missing log just means no games have been played yet
+fn log_lines() -> Result<Box<dyn Iterator<Item = Result<String, StorageError>>>, StorageError> {
+    match storage::lib::read_lines(&history_path()) {
+        Ok(lines) => Ok(Box::new(lines)),
+        Err(StorageError::NotFound(_)) => Ok(Box::new(std::iter::empty())),
+        Err(e) => Err(e),
+    }
+}
+
+// the lines that could be read, for anything that only looks at the games
+fn records() -> Result<impl Iterator<Item = String>, StorageError> {
+    Ok(log_lines()?.filter_map(Result::ok))
+}
+
+fn deleted_ids() -> Result<HashSet<i32>, StorageError> {
+    Ok(records()?
+        .filter_map(|line| match parse_line(&line) {
+            Some(HistoryRecord::Deleted(id)) => Some(id),
+            _ => None,
+        })
+        .collect())
+}
+
+// streams matching games, only the ids of deleted ones are held in memory
+pub fn query(query: HistoryQuery) -> Result<impl Iterator<Item = GameData>, StorageError> {
+    let deleted = deleted_ids()?;
+    Ok(records()?
+        .filter_map(|line| match parse_line(&line) {
+            Some(HistoryRecord::Game(game)) => Some(game),
+            _ => None,
+        })
+        .filter(move |game| !deleted.contains(&game.id) && query.matches(game)))
+}
+
+// recomputes the bests and totals from what is left in the log
+pub fn rebuild_summary(save_data: &mut SaveData) -> Result<(), StorageError> {
+    save_data.best_game = GameData::new();
+    save_data.best_games = vec![];
+    save_data.totals = HistoryTotals::default();
+    for game in query(HistoryQuery::default())? {
+        save_data.record(&game);
+    }
+    Ok(())
+}
+
+// the lines worth keeping, or None when every line already is, a None line wasn't text
+fn compacted(lines: Vec<Option<String>>) -> Option<String> {
+    let records: Vec<Option<HistoryRecord>> =
+        lines.iter().map(|line| line.as_deref().and_then(parse_line)).collect();
+    let deleted: HashSet<i32> = records
+        .iter()
+        .filter_map(|record| match record {
+            Some(HistoryRecord::Deleted(id)) => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if deleted.is_empty() && records.iter().all(|record| record.is_some()) {
+        return None;
+    }
+
+    let mut kept = String::new();
+    for record in records.into_iter().flatten() {
+        if let HistoryRecord::Game(ref game) = record {
+            if !deleted.contains(&game.id) {
+                kept.push_str(&write_line(&record));
+                kept.push('\n');
+            }
+        }
+    }
+    Some(kept)
+}
+
+// drops deleted games and lines that can't be read, such as one left half written by a crash
+pub fn compact() -> Result<bool, StorageError> {
+    let mut lines = vec![];
+    for line in log_lines()? {
+        match line {
+            Ok(line) => lines.push(Some(line)),
+            Err(StorageError::Corrupt(_)) => lines.push(None),
+            // the rest of the log wasn't read, so rewriting it would lose those games
+            Err(e) => return Err(e),
+        }
+    }
+
+    match compacted(lines) {
+        Some(data) => {
+            storage::lib::save(&history_path(), &data)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn game(id: i32, mode: GameMode, end_time: &str) -> GameData {
+        let mut game = GameData::new();
+        game.id = id;
+        game.mode = mode;
+        game.end_time = end_time.parse().unwrap();
+        game
+    }
+
+    #[test]
+    fn it_does_filter_by_mode_and_date() {
+        let query = HistoryQuery {
+            mode: Some(GameMode::Dig),
+            from: Some("2024-10-01T00:00:00Z".parse().unwrap()),
+            to: Some("2024-10-02T00:00:00Z".parse().unwrap()),
+        };
+
+        assert!(query.matches(&game(1, GameMode::Dig, "2024-10-01T12:00:00Z")));
+        assert!(!query.matches(&game(2, GameMode::Zen, "2024-10-01T12:00:00Z")));
+        assert!(!query.matches(&game(3, GameMode::Dig, "2024-10-02T00:00:00Z")));
+        assert!(HistoryQuery::default().matches(&game(4, GameMode::Zen, "2020-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn it_does_compact_unreadable_lines() {
+        let good = write_line(&HistoryRecord::Game(game(1, GameMode::Zen, "2024-10-01T12:00:00Z")));
+        let lines = vec![Some(good.clone()), Some("(Game((id:2,sco".to_string())];
+
+        assert_eq!(compacted(lines), Some(format!("{}\n", good)));
+        assert_eq!(compacted(vec![Some(good)]), None);
+    }
+
+    #[test]
+    fn it_does_compact_deleted_games() {
+        let kept = write_line(&HistoryRecord::Game(game(1, GameMode::Zen, "2024-10-01T12:00:00Z")));
+        let lines = vec![
+            Some(kept.clone()),
+            Some(write_line(&HistoryRecord::Game(game(2, GameMode::Dig, "2024-10-02T12:00:00Z")))),
+            Some(write_line(&HistoryRecord::Deleted(2))),
+        ];
+
+        assert_eq!(compacted(lines), Some(format!("{}\n", kept)));
+    }
+
+    #[test]
+    fn it_does_keep_the_games_after_a_line_that_is_not_text() {
+        let first = write_line(&HistoryRecord::Game(game(1, GameMode::Zen, "2024-10-01T12:00:00Z")));
+        let last = write_line(&HistoryRecord::Game(game(3, GameMode::Dig, "2024-10-03T12:00:00Z")));
+        let lines = vec![Some(first.clone()), None, Some(last.clone())];
+
+        assert_eq!(compacted(lines), Some(format!("{}\n{}\n", first, last)));
+    }
+
+    #[test]
+    fn it_does_merge_only_unrecorded_games() {
+        let lines = vec![
+            write_line(&HistoryRecord::Game(game(1, GameMode::Zen, "2024-10-01T12:00:00Z"))),
+            write_line(&HistoryRecord::Deleted(2)),
+            write_line(&HistoryRecord::Game(game(4, GameMode::Dig, "2024-10-04T12:00:00Z"))),
+        ];
+        let games = vec![
+            game(1, GameMode::Zen, "2024-10-01T12:00:00Z"),
+            game(2, GameMode::Dig, "2024-10-02T12:00:00Z"),
+            game(3, GameMode::Marathon, "2024-10-03T12:00:00Z"),
+        ];
+
+        let ids: Vec<i32> = unrecorded(lines.into_iter(), &games).iter().map(|game| game.id).collect();
+        assert_eq!(ids, vec![3]);
+    }
+
+    #[test]
+    fn it_does_sort_games() {
+        let mut games = vec![
+            game(1, GameMode::Zen, "2024-10-01T12:00:00Z"),
+            game(2, GameMode::Dig, "2024-10-02T12:00:00Z"),
+        ];
+        games[0].score = 500;
+        games[1].score = 900;
+
+        sort_games(&mut games, HistoryColumn::Score, true);
+        assert_eq!(games[0].id, 2);
+        sort_games(&mut games, HistoryColumn::Mode, false);
+        assert_eq!(games[0].mode, GameMode::Dig);
+    }
+}
@@ -1,36 +1,281 @@
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+
 use platform_dirs::AppDirs;
 
-use crate::engine::managers::game_statics::read_game_statics;
+use crate::engine::{common::storage::error::StorageError, managers::game_statics::read_game_statics};
 
-fn get_app_data_dir() -> String {
-    let app_dirs = AppDirs::new(Some(&read_game_statics().url), true).unwrap();
-    app_dirs.data_dir.to_str().unwrap().to_string()
+fn get_app_data_dir() -> Result<String, StorageError> {
+    let app_dirs =
+        AppDirs::new(Some(&read_game_statics().url), true).ok_or(StorageError::MissingDir)?;
+    Ok(app_dirs.data_dir.to_string_lossy().to_string())
 }
 
-pub fn load(path: &str) -> String {
-    let path = format!("{}/{}", get_app_data_dir(), path);
-    println!("{}", path);
-    if let Some(parent_dir) = std::path::Path::new(&path).parent() {
+fn create_parent_dir(path: &str) -> Result<(), StorageError> {
+    if let Some(parent_dir) = std::path::Path::new(path).parent() {
         if !parent_dir.exists() {
-            std::fs::create_dir_all(parent_dir).unwrap();
+            std::fs::create_dir_all(parent_dir)
+                .map_err(|e| StorageError::from_io(&parent_dir.to_string_lossy(), e))?;
         }
     }
-    std::fs::read_to_string(path).unwrap_or_default()
+    Ok(())
 }
 
-pub fn save(path: &str, data: &str) {
-    let path = format!("{}/{}", get_app_data_dir(), path);
-    if let Some(parent_dir) = std::path::Path::new(&path).parent() {
-        if !parent_dir.exists() {
-            std::fs::create_dir_all(parent_dir).unwrap();
+// a missing file is NotFound, anything else that stops it being read is a different error
+pub fn load(path: &str) -> Result<String, StorageError> {
+    let path = full_path(path)?;
+    create_parent_dir(&path)?;
+    std::fs::read_to_string(&path).map_err(|e| StorageError::from_io(&path, e))
+}
+
+pub fn save(path: &str, data: &str) -> Result<(), StorageError> {
+    let path = full_path(path)?;
+    create_parent_dir(&path)?;
+    // write next to the live file first so a crash mid write never leaves it half written
+    let temp_path = format!("{}.tmp", path);
+    let mut file =
+        std::fs::File::create(&temp_path).map_err(|e| StorageError::from_io(&temp_path, e))?;
+    file.write_all(data.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| StorageError::from_io(&temp_path, e))?;
+    std::fs::rename(&temp_path, &path).map_err(|e| StorageError::from_io(&path, e))
+}
+
+pub fn append_line(path: &str, line: &str) -> Result<(), StorageError> {
+    let path = full_path(path)?;
+    create_parent_dir(&path)?;
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(&path)
+        .map_err(|e| StorageError::from_io(&path, e))?;
+
+    // a write cut short last time leaves no newline, so start a fresh line instead of joining it
+    let mut data = String::new();
+    let len = file.metadata().map_err(|e| StorageError::from_io(&path, e))?.len();
+    if len > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))
+            .and_then(|_| file.read_exact(&mut last))
+            .map_err(|e| StorageError::from_io(&path, e))?;
+        if last[0] != b'\n' {
+            data.push('\n');
+        }
+    }
+    data.push_str(line);
+    data.push('\n');
+    file.write_all(data.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| StorageError::from_io(&path, e))
+}
+
+// streams a file line by line rather than reading it all at once, a line that isn't text is
+// Corrupt and reading carries on past it, a failed read is the last item
+pub fn read_lines(path: &str) -> Result<impl Iterator<Item = Result<String, StorageError>>, StorageError> {
+    let path = full_path(path)?;
+    let file = std::fs::File::open(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    Ok(lines_of(path, BufReader::new(file)))
+}
+
+fn lines_of(path: String, mut reader: impl BufRead) -> impl Iterator<Item = Result<String, StorageError>> {
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        let mut line = vec![];
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                if line.ends_with(b"\n") {
+                    line.pop();
+                }
+                if line.ends_with(b"\r") {
+                    line.pop();
+                }
+                Some(
+                    String::from_utf8(line)
+                        .map_err(|_| StorageError::Corrupt(format!("a line of {} is not valid text", path))),
+                )
+            }
+            // never Corrupt, so it can't be mistaken for a single bad line
+            Err(e) => {
+                failed = true;
+                Some(Err(StorageError::Other(path.clone(), e.to_string())))
+            }
+        }
+    })
+}
+
+pub fn delete(path: &str) -> Result<(), StorageError> {
+    let path = full_path(path)?;
+    if std::path::Path::new(&path).exists() {
+        std::fs::remove_file(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    }
+    Ok(())
+}
+
+pub fn list(path: &str) -> Result<Vec<String>, StorageError> {
+    let path = full_path(path)?;
+    if !std::path::Path::new(&path).exists() {
+        std::fs::create_dir_all(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    }
+    let mut files = vec![];
+    let entries = std::fs::read_dir(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    for entry in entries.flatten() {
+        if entry.path().is_file() {
+            files.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    Ok(files)
+}
+
+pub fn full_path(path: &str) -> Result<String, StorageError> {
+    Ok(format!("{}/{}", get_app_data_dir()?, path))
+}
+
+pub fn exists(path: &str) -> bool {
+    full_path(path).is_ok_and(|path| std::path::Path::new(&path).exists())
+}
+
+pub fn rename(from: &str, to: &str) -> Result<(), StorageError> {
+    let from = full_path(from)?;
+    let to = full_path(to)?;
+    if std::path::Path::new(&from).exists() {
+        create_parent_dir(&to)?;
+        std::fs::rename(&from, to).map_err(|e| StorageError::from_io(&from, e))?;
+    }
+    Ok(())
+}
+
+pub fn list_dirs(path: &str) -> Result<Vec<String>, StorageError> {
+    let path = full_path(path)?;
+    if !std::path::Path::new(&path).exists() {
+        return Ok(vec![]);
+    }
+    let mut dirs = vec![];
+    let entries = std::fs::read_dir(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    for entry in entries.flatten() {
+        if entry.path().is_dir() {
+            dirs.push(entry.file_name().to_string_lossy().to_string());
         }
     }
-    std::fs::write(path, data).unwrap();
+    Ok(dirs)
 }
 
-pub fn delete(path: &str) {
-    let path = format!("{}/{}", get_app_data_dir(), path);
+pub fn delete_dir(path: &str) -> Result<(), StorageError> {
+    let path = full_path(path)?;
     if std::path::Path::new(&path).exists() {
-        std::fs::remove_file(path).unwrap();
+        std::fs::remove_dir_all(&path).map_err(|e| StorageError::from_io(&path, e))?;
+    }
+    Ok(())
+}
+
+pub fn copy(from: &str, to: &str) -> Result<(), StorageError> {
+    let from = full_path(from)?;
+    let to = full_path(to)?;
+    if std::path::Path::new(&from).exists() {
+        create_parent_dir(&to)?;
+        std::fs::copy(&from, &to).map_err(|e| StorageError::from_io(&to, e))?;
+    }
+    Ok(())
+}
+
+fn backup_dir(path: &str) -> String {
+    match path.rfind('/') {
+        Some(index) => format!("{}/backups", &path[..index]),
+        None => "backups".to_string(),
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+// backups are named <file>.<timestamp> so sorting by name sorts them by age
+pub fn list_backups(path: &str) -> Result<Vec<String>, StorageError> {
+    let prefix = format!("{}.", file_name(path));
+    let mut backups: Vec<String> = list(&backup_dir(path))?
+        .into_iter()
+        .filter(|name| name.starts_with(&prefix) && !name.ends_with(".tmp"))
+        .collect();
+    backups.sort();
+    backups.reverse();
+    Ok(backups)
+}
+
+// everything past the newest `keep` backups
+fn stale_backups(backups: &[String], keep: usize) -> Vec<String> {
+    backups.iter().skip(keep).cloned().collect()
+}
+
+pub fn backup(path: &str, keep: usize) -> Result<(), StorageError> {
+    if !exists(path) {
+        return Ok(());
+    }
+    let dir = backup_dir(path);
+    let name = format!(
+        "{}.{}",
+        file_name(path),
+        chrono::offset::Utc::now().format("%Y%m%d%H%M%S%3f")
+    );
+    copy(path, &format!("{}/{}", dir, name))?;
+
+    for stale in stale_backups(&list_backups(path)?, keep) {
+        delete(&format!("{}/{}", dir, stale))?;
+    }
+    Ok(())
+}
+
+pub fn load_backup(path: &str, backup: &str) -> Result<String, StorageError> {
+    load(&format!("{}/{}", backup_dir(path), backup))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_keep_the_newest_backups() {
+        let backups = vec![
+            "save.rvrs.20241003000000000".to_string(),
+            "save.rvrs.20241002000000000".to_string(),
+            "save.rvrs.20241001000000000".to_string(),
+        ];
+
+        assert_eq!(
+            stale_backups(&backups, 2),
+            vec!["save.rvrs.20241001000000000".to_string()]
+        );
+        assert!(stale_backups(&backups, 5).is_empty());
+        assert_eq!(backup_dir("profiles/cat/save.rvrs"), "profiles/cat/backups");
+        assert_eq!(backup_dir("save.rvrs"), "backups");
+    }
+
+    #[test]
+    fn it_does_read_past_a_line_that_is_not_text() {
+        let data = b"first\r\nbad \xff line\nlast".to_vec();
+        let lines: Vec<Result<String, StorageError>> =
+            lines_of("history.rvlog".to_string(), std::io::Cursor::new(data)).collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], Ok("first".to_string()));
+        assert!(matches!(lines[1], Err(StorageError::Corrupt(_))));
+        assert_eq!(lines[2], Ok("last".to_string()));
+    }
+
+    #[test]
+    fn it_does_tell_missing_from_unreadable() {
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+
+        assert_eq!(
+            StorageError::from_io("save.rvrs", missing),
+            StorageError::NotFound("save.rvrs".to_string())
+        );
+        assert_eq!(
+            StorageError::from_io("save.rvrs", denied),
+            StorageError::PermissionDenied("save.rvrs".to_string())
+        );
     }
 }
@@ -0,0 +1,241 @@
+use serde::{de::IgnoredAny, Deserialize};
+
+use crate::engine::managers::game_manager::{GameData, SaveData};
+
+// bump this and add a frozen copy of the old layout below whenever SaveData or GameData change
+// in a way that serde defaults can't fill in
+pub const CURRENT_SAVE_VERSION: u32 = 2;
+
+#[derive(Deserialize)]
+struct SaveVersion {
+    // saves from before versioning don't have the field
+    #[serde(default)]
+    version: u32,
+}
+
+// only the fields saves had before they were sealed, so a newer save can't pass for one by
+// dropping its version and zeroing its check sum
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyShape {
+    check_sum: i32,
+    #[serde(rename = "best_game")]
+    _best_game: IgnoredAny,
+    #[serde(rename = "history")]
+    _history: IgnoredAny,
+    #[serde(default, rename = "solved_puzzles")]
+    _solved_puzzles: IgnoredAny,
+}
+
+// a save written before saves were sealed, which never had its check sum filled in
+pub fn is_unsealed_legacy(data: &str) -> bool {
+    ron::de::from_str::<LegacyShape>(data).is_ok_and(|legacy| legacy.check_sum == 0)
+}
+
+pub fn detect_version(data: &str) -> Result<u32, String> {
+    ron::de::from_str::<SaveVersion>(data)
+        .map(|save_version| save_version.version)
+        .map_err(|e| format!("it could not be read ({})", e))
+}
+
+// reads a save of any known version and upgrades it one step at a time to the current layout,
+// along with any history it held that still has to be written to the history log
+pub fn migrate(data: &str, version: u32) -> Result<(SaveData, Vec<GameData>), String> {
+    let parse_error = |e: ron::error::SpannedError| format!("it could not be read ({})", e);
+    match version {
+        0 => Ok(v1::upgrade(v0::upgrade(
+            ron::de::from_str(data).map_err(parse_error)?,
+        ))),
+        1 => Ok(v1::upgrade(ron::de::from_str(data).map_err(parse_error)?)),
+        CURRENT_SAVE_VERSION => Ok((ron::de::from_str(data).map_err(parse_error)?, vec![])),
+        newer => Err(format!(
+            "it was written by a newer version of the game (save version {})",
+            newer
+        )),
+    }
+}
+
+// the layout before saves had a version, including the fields that were added with defaults
+mod v0 {
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct SaveData {
+        pub check_sum: i32,
+        pub best_game: GameData,
+        pub history: Vec<GameData>,
+        #[serde(default)]
+        pub solved_puzzles: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct GameData {
+        pub id: i32,
+        pub score: i32,
+        pub level: i32,
+        pub lines_cleared: i32,
+        pub start_time: DateTime<Utc>,
+        pub end_time: DateTime<Utc>,
+        #[serde(default)]
+        pub mode: GameMode,
+        #[serde(default)]
+        pub pieces_placed: i32,
+        #[serde(default)]
+        pub ticks_played: i32,
+        #[serde(default)]
+        pub seed: u64,
+        #[serde(default)]
+        pub garbage_cleared: i32,
+    }
+
+    #[derive(Deserialize, Default)]
+    pub enum GameMode {
+        #[default]
+        Marathon,
+        Zen,
+        Dig,
+        Survival,
+        Puzzle,
+    }
+
+    pub fn upgrade(save_data: SaveData) -> super::v1::SaveData {
+        super::v1::SaveData {
+            check_sum: save_data.check_sum,
+            best_game: save_data.best_game,
+            history: save_data.history,
+            solved_puzzles: save_data.solved_puzzles,
+        }
+    }
+}
+
+// the last layout that kept the whole history inside the save
+mod v1 {
+    use serde::Deserialize;
+
+    use super::v0::{GameData, GameMode};
+    use crate::engine::managers::game_manager;
+
+    #[derive(Deserialize)]
+    pub struct SaveData {
+        pub check_sum: i32,
+        pub best_game: GameData,
+        pub history: Vec<GameData>,
+        #[serde(default)]
+        pub solved_puzzles: Vec<String>,
+    }
+
+    fn upgrade_game(game: GameData) -> game_manager::GameData {
+        game_manager::GameData {
+            id: game.id,
+            score: game.score,
+            level: game.level,
+            lines_cleared: game.lines_cleared,
+            start_time: game.start_time,
+            end_time: game.end_time,
+            mode: match game.mode {
+                GameMode::Marathon => game_manager::GameMode::Marathon,
+                GameMode::Zen => game_manager::GameMode::Zen,
+                GameMode::Dig => game_manager::GameMode::Dig,
+                GameMode::Survival => game_manager::GameMode::Survival,
+                GameMode::Puzzle => game_manager::GameMode::Puzzle,
+            },
+            pieces_placed: game.pieces_placed,
+            ticks_played: game.ticks_played,
+            seed: game.seed,
+            garbage_cleared: game.garbage_cleared,
+        }
+    }
+
+    // the history moves out to the log, the save keeps only what it adds up to
+    pub fn upgrade(save_data: SaveData) -> (game_manager::SaveData, Vec<game_manager::GameData>) {
+        let history: Vec<game_manager::GameData> =
+            save_data.history.into_iter().map(upgrade_game).collect();
+        let mut upgraded = game_manager::SaveData {
+            version: 2,
+            check_sum: save_data.check_sum,
+            best_game: upgrade_game(save_data.best_game),
+            best_games: vec![],
+            totals: game_manager::HistoryTotals::default(),
+            solved_puzzles: save_data.solved_puzzles,
+            leaderboards: vec![],
+            player_name: "".to_string(),
+            custom_blocks: vec![],
+            settings: game_manager::Settings::default(),
+        };
+        for game in history.iter() {
+            upgraded.record(game);
+        }
+        (upgraded, history)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::managers::game_manager::GameMode;
+
+    // written by the first release, before any of the optional fields existed
+    const V0_ORIGINAL: &str = r#"(check_sum:0,best_game:(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z"),history:[(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z")])"#;
+
+    // written after game modes and puzzles were added, still without a version
+    const V0_MODES: &str = r#"(check_sum:0,best_game:(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z",mode:Marathon,pieces_placed:40,ticks_played:18000,seed:0,garbage_cleared:0),history:[(id:13,score:100,level:1,lines_cleared:12,start_time:"2024-10-02T10:00:00Z",end_time:"2024-10-02T10:02:00Z",mode:Dig,pieces_placed:30,ticks_played:7200,seed:99,garbage_cleared:10)],solved_puzzles:["well"])"#;
+
+    const V1: &str = r#"(version:1,check_sum:0,best_game:(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z",mode:Zen,pieces_placed:40,ticks_played:18000,seed:0,garbage_cleared:0),history:[(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z",mode:Zen,pieces_placed:40,ticks_played:18000,seed:0,garbage_cleared:0),(id:14,score:300,level:1,lines_cleared:3,start_time:"2024-10-03T10:00:00Z",end_time:"2024-10-03T10:01:00Z",mode:Marathon,pieces_placed:12,ticks_played:3600,seed:0,garbage_cleared:0)],solved_puzzles:["well"])"#;
+
+    const V2: &str = r#"(version:2,check_sum:0,best_game:(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z",mode:Zen,pieces_placed:40,ticks_played:18000,seed:0,garbage_cleared:0),best_games:[],totals:(games_played:2,score:1200,lines_cleared:10,pieces_placed:52,ticks_played:21600),solved_puzzles:[])"#;
+
+    fn load(data: &str) -> Result<(SaveData, Vec<GameData>), String> {
+        migrate(data, detect_version(data)?)
+    }
+
+    #[test]
+    fn it_does_load_an_original_save() {
+        let (save_data, history) = load(V0_ORIGINAL).unwrap();
+        assert_eq!(save_data.version, CURRENT_SAVE_VERSION);
+        assert_eq!(save_data.best_game.score, 900);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].pieces_placed, 0);
+        assert_eq!(save_data.totals.games_played, 1);
+    }
+
+    #[test]
+    fn it_does_load_an_unversioned_save_with_modes() {
+        let (save_data, history) = load(V0_MODES).unwrap();
+        assert_eq!(save_data.version, CURRENT_SAVE_VERSION);
+        assert_eq!(history[0].mode, GameMode::Dig);
+        assert_eq!(history[0].garbage_cleared, 10);
+        assert_eq!(save_data.best_games[0].mode, GameMode::Dig);
+        assert_eq!(save_data.solved_puzzles, vec!["well".to_string()]);
+    }
+
+    #[test]
+    fn it_does_move_v1_history_out_of_the_save() {
+        let (save_data, history) = load(V1).unwrap();
+        assert_eq!(save_data.version, CURRENT_SAVE_VERSION);
+        assert_eq!(save_data.best_game.mode, GameMode::Zen);
+        assert_eq!(history.len(), 2);
+        assert_eq!(save_data.best_games.len(), 2);
+        assert_eq!(save_data.totals.score, 1200);
+        assert_eq!(save_data.totals.ticks_played, 21600);
+    }
+
+    #[test]
+    fn it_does_load_a_v2_save() {
+        let (save_data, history) = load(V2).unwrap();
+        assert_eq!(save_data.totals.games_played, 2);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn it_does_round_trip_the_current_version() {
+        let data = ron::ser::to_string(&SaveData::new()).unwrap();
+        assert_eq!(detect_version(&data).unwrap(), CURRENT_SAVE_VERSION);
+        assert!(load(&data).is_ok());
+    }
+
+    #[test]
+    fn it_does_refuse_a_newer_save() {
+        assert!(load(&V2.replace("version:2", "version:99")).is_err());
+    }
+}
@@ -1 +1,6 @@
+pub mod error;
+pub mod history;
 pub mod lib;
+pub mod migrations;
+pub mod profile;
+pub mod save_file;
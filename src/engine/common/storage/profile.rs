@@ -0,0 +1,191 @@
+use std::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{
+    common::storage::{
+        self,
+        error::StorageError,
+        history::HISTORY_FILE,
+        save_file::{LEGACY_BACKUP_FILE, SAVE_FILE},
+    },
+    managers::game_manager::write_game_manager_notice,
+};
+
+pub const PROFILES_DIR: &str = "profiles";
+const PROFILES_FILE: &str = "profiles.ron";
+
+// everything that lived at the top of the data dir before there were profiles
+const LEGACY_FILES: [&str; 4] = [SAVE_FILE, LEGACY_BACKUP_FILE, HISTORY_FILE, "backups"];
+const LEGACY_PROFILE_NAME: &str = "Player 1";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Profile {
+    // the directory name, kept when the profile is renamed
+    pub id: String,
+    pub name: String,
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProfileIndex {
+    pub profiles: Vec<Profile>,
+    #[serde(default)]
+    pub last_used: Option<String>,
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    }
+}
+
+impl ProfileIndex {
+    pub fn find(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.id == id)
+    }
+
+    pub fn name_taken(&self, name: &str, except: Option<&str>) -> bool {
+        self.profiles.iter().any(|profile| {
+            profile.name.eq_ignore_ascii_case(name.trim()) && Some(profile.id.as_str()) != except
+        })
+    }
+
+    pub fn create(&mut self, name: &str) -> Profile {
+        let base = slug(name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.find(&id).is_some() {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        let profile = Profile {
+            id,
+            name: name.trim().to_string(),
+            created: Utc::now(),
+        };
+        self.profiles.push(profile.clone());
+        profile
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) {
+        if let Some(profile) = self.profiles.iter_mut().find(|profile| profile.id == id) {
+            profile.name = name.trim().to_string();
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.profiles.retain(|profile| profile.id != id);
+        if self.last_used.as_deref() == Some(id) {
+            self.last_used = None;
+        }
+    }
+}
+
+static ACTIVE_PROFILE: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+pub fn set_active(id: Option<String>) {
+    *ACTIVE_PROFILE.write().unwrap() = id;
+}
+
+pub fn active() -> Option<String> {
+    ACTIVE_PROFILE.read().unwrap().clone()
+}
+
+pub fn profile_path(id: &str, file: &str) -> String {
+    format!("{}/{}/{}", PROFILES_DIR, id, file)
+}
+
+// where a per profile file lives for whoever is playing
+pub fn active_path(file: &str) -> String {
+    match active() {
+        Some(id) => profile_path(&id, file),
+        None => file.to_string(),
+    }
+}
+
+pub fn save_index(index: &ProfileIndex) -> Result<(), StorageError> {
+    storage::lib::save(PROFILES_FILE, &ron::ser::to_string(index).unwrap())
+}
+
+// rebuilds the index from the profile directories, moving a save from before profiles into one
+fn rebuild_index() -> Result<ProfileIndex, StorageError> {
+    let mut index = ProfileIndex::default();
+    let mut dirs = storage::lib::list_dirs(PROFILES_DIR)?;
+    dirs.sort();
+    for id in dirs {
+        index.profiles.push(Profile {
+            name: id.clone(),
+            id,
+            created: Utc::now(),
+        });
+    }
+
+    if LEGACY_FILES.iter().any(|file| storage::lib::exists(file)) {
+        let profile = index.create(LEGACY_PROFILE_NAME);
+        for file in LEGACY_FILES {
+            storage::lib::rename(file, &profile_path(&profile.id, file))?;
+        }
+        index.last_used = Some(profile.id);
+    }
+    if !index.profiles.is_empty() {
+        save_index(&index)?;
+    }
+    Ok(index)
+}
+
+pub fn load_index() -> Result<ProfileIndex, StorageError> {
+    match storage::lib::load(PROFILES_FILE) {
+        Ok(data) => match ron::de::from_str(&data) {
+            Ok(index) => Ok(index),
+            Err(e) => {
+                write_game_manager_notice(Some(format!(
+                    "The profile list could not be read because {}, so it was rebuilt from the profile folders.",
+                    e
+                )));
+                rebuild_index()
+            }
+        },
+        Err(StorageError::NotFound(_)) => rebuild_index(),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn delete_files(id: &str) -> Result<(), StorageError> {
+    storage::lib::delete_dir(&format!("{}/{}", PROFILES_DIR, id))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_keep_ids_unique_and_stable() {
+        let mut index = ProfileIndex::default();
+        let first = index.create("Cat Luna");
+        let second = index.create("cat luna!");
+        index.rename(&first.id, "Luna");
+
+        assert_eq!(first.id, "cat-luna");
+        assert_eq!(second.id, "cat-luna-2");
+        assert_eq!(index.find("cat-luna").unwrap().name, "Luna");
+        assert!(index.name_taken("luna", None));
+        assert!(!index.name_taken("luna", Some("cat-luna")));
+
+        index.last_used = Some(second.id.clone());
+        index.remove(&second.id);
+        assert!(index.last_used.is_none());
+        assert_eq!(index.profiles.len(), 1);
+    }
+}
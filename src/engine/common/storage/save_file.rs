@@ -0,0 +1,284 @@
+use crate::engine::{
+    common::storage::{self, error::StorageError, history, migrations, profile},
+    managers::{
+        game_manager::{GameData, SaveData},
+        game_statics::read_game_statics,
+    },
+};
+
+pub const SAVE_FILE: &str = "save.rvrs";
+// the single backup kept before rotating backups, still tried when recovering
+pub const LEGACY_BACKUP_FILE: &str = "save.rvrs.bak";
+
+const CHECK_SUM_FIELD: &str = "check_sum:";
+
+// the save of whichever profile is active
+fn save_path() -> String {
+    profile::active_path(SAVE_FILE)
+}
+
+pub enum LoadOutcome {
+    New(SaveData),
+    Loaded(SaveData),
+    // the save was unusable, so it was moved aside and this is what replaced it
+    Recovered(SaveData, String),
+    // the save couldn't be read or written, so it is left alone and nothing gets saved
+    Unavailable(SaveData, String),
+}
+
+// 32 bit FNV-1a, stored in the i32 check_sum field
+pub fn checksum(payload: &str) -> i32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in payload.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash as i32
+}
+
+// returns the stored check_sum and the payload with it zeroed, which is what the sum covers
+fn split_check_sum(data: &str) -> Option<(i32, String)> {
+    let start = data.find(CHECK_SUM_FIELD)? + CHECK_SUM_FIELD.len();
+    let rest = &data[start..];
+    let end = rest
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && c == '-')))
+        .map(|(i, _)| i)
+        .unwrap_or(rest.len());
+    let stored = rest[..end].parse::<i32>().ok()?;
+    Some((stored, format!("{}0{}", &data[..start], &rest[end..])))
+}
+
+pub fn seal(save_data: &SaveData) -> String {
+    let mut save_data = save_data.clone();
+    save_data.check_sum = 0;
+    let payload = ron::ser::to_string(&save_data).unwrap();
+    let sum = checksum(&payload);
+    payload.replacen(
+        &format!("{}0", CHECK_SUM_FIELD),
+        &format!("{}{}", CHECK_SUM_FIELD, sum),
+        1,
+    )
+}
+
+// also hands back any history an older save held that hasn't been moved to the log yet
+fn open_migrated(data: &str) -> Result<(SaveData, Vec<GameData>), StorageError> {
+    let (stored, payload) = split_check_sum(data)
+        .ok_or_else(|| StorageError::Corrupt("the check sum is missing".to_string()))?;
+    let version = migrations::detect_version(data).map_err(StorageError::Corrupt)?;
+    // saves from before sealing were written with the check sum left at 0
+    if !migrations::is_unsealed_legacy(data) && stored != checksum(&payload) {
+        return Err(StorageError::Corrupt(
+            "the check sum does not match".to_string(),
+        ));
+    }
+    migrations::migrate(data, version).map_err(StorageError::Corrupt)
+}
+
+pub fn save(save_data: &SaveData) -> Result<(), StorageError> {
+    // keep the last good saves around in case this one gets damaged
+    let backup_count = read_game_statics().backup_count;
+    storage::lib::backup(&save_path(), backup_count)?;
+    storage::lib::save(&save_path(), &seal(save_data))
+}
+
+pub fn list_backups() -> Result<Vec<String>, StorageError> {
+    storage::lib::list_backups(&save_path())
+}
+
+// when the backup was taken, from the timestamp in its name
+pub fn backup_label(backup: &str) -> String {
+    let stamp = backup.rsplit('.').next().unwrap_or(backup);
+    match chrono::NaiveDateTime::parse_from_str(stamp.get(..14).unwrap_or(stamp), "%Y%m%d%H%M%S") {
+        Ok(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+        Err(_) => backup.to_string(),
+    }
+}
+
+// games an older save held go into the log, and the summary is worked out again from the log
+// since it may hold games newer than the save
+fn adopt_history(save_data: &mut SaveData, history: &[GameData]) -> Result<(), StorageError> {
+    history::merge(history)?;
+    history::rebuild_summary(save_data)
+}
+
+// only restores backups that open, so a damaged backup can't replace the live save. Backups from
+// older versions are migrated, and the live save is backed up before it is replaced
+pub fn restore_backup(backup: &str) -> Result<SaveData, StorageError> {
+    let (mut save_data, history) = open_migrated(&storage::lib::load_backup(&save_path(), backup)?)?;
+    adopt_history(&mut save_data, &history)?;
+    save(&save_data)?;
+    Ok(save_data)
+}
+
+// the first backup that still opens, migrated along with any history it held
+fn first_valid(
+    mut backups: impl Iterator<Item = Result<String, StorageError>>,
+) -> Option<(SaveData, Vec<GameData>)> {
+    backups.find_map(|data| open_migrated(&data.ok()?).ok())
+}
+
+// the newest backup that still opens
+fn newest_valid_backup() -> Option<(SaveData, Vec<GameData>)> {
+    let legacy_backup = storage::lib::load(&profile::active_path(LEGACY_BACKUP_FILE));
+    first_valid(
+        list_backups()
+            .unwrap_or_default()
+            .iter()
+            .map(|backup| storage::lib::load_backup(&save_path(), backup))
+            .chain(std::iter::once(legacy_backup)),
+    )
+}
+
+fn unavailable(reason: StorageError) -> LoadOutcome {
+    LoadOutcome::Unavailable(
+        SaveData::new(),
+        format!(
+            "Your save file could not be used because {}. It has been left untouched and nothing will be saved until the game is restarted.",
+            reason
+        ),
+    )
+}
+
+pub fn load() -> LoadOutcome {
+    // only a save that isn't there at all means a new player
+    let data = match storage::lib::load(&save_path()) {
+        Ok(data) => data,
+        Err(StorageError::NotFound(_)) => {
+            let save_data = SaveData::new();
+            return match save(&save_data) {
+                Ok(()) => LoadOutcome::New(save_data),
+                Err(e) => unavailable(e),
+            };
+        }
+        Err(e) => return unavailable(e),
+    };
+
+    let reason = match open_migrated(&data) {
+        Ok((mut save_data, history)) => {
+            // write older layouts back out in the current one, history first so a crash in
+            // between just migrates again next time
+            if migrations::detect_version(&data) != Ok(migrations::CURRENT_SAVE_VERSION) {
+                let migrated = adopt_history(&mut save_data, &history).and_then(|_| save(&save_data));
+                if let Err(e) = migrated {
+                    return unavailable(e);
+                }
+            }
+            return LoadOutcome::Loaded(save_data);
+        }
+        Err(reason) => reason,
+    };
+
+    let moved_to = format!(
+        "{}.corrupt-{}",
+        save_path(),
+        chrono::offset::Utc::now().format("%Y%m%d%H%M%S")
+    );
+    if let Err(e) = storage::lib::rename(&save_path(), &moved_to) {
+        return unavailable(e);
+    }
+
+    let (mut save_data, history, notice) = match newest_valid_backup() {
+        Some((save_data, history)) => (
+            save_data,
+            history,
+            format!(
+                "Your save file was damaged because {}, so it was restored from a backup. The damaged file was kept as {}.",
+                reason, moved_to
+            ),
+        ),
+        None => (
+            SaveData::new(),
+            vec![],
+            format!(
+                "Your save file was damaged because {} and no usable backup was found, so a new one was started. The damaged file was kept as {}.",
+                reason, moved_to
+            ),
+        ),
+    };
+    // a backup in an older layout still holds its games, so they go to the log like on a load
+    match adopt_history(&mut save_data, &history).and_then(|_| save(&save_data)) {
+        Ok(()) => LoadOutcome::Recovered(save_data, notice),
+        Err(e) => unavailable(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn open(data: &str) -> Result<SaveData, StorageError> {
+        open_migrated(data).map(|(save_data, _)| save_data)
+    }
+
+    #[test]
+    fn it_does_open_a_sealed_save() {
+        let mut save_data = SaveData::new();
+        save_data.best_game.score = 1200;
+        let sealed = seal(&save_data);
+
+        assert!(!sealed.contains("check_sum:0,"));
+        assert_eq!(open(&sealed).unwrap().best_game.score, 1200);
+    }
+
+    #[test]
+    fn it_does_reject_an_edited_save() {
+        let mut save_data = SaveData::new();
+        save_data.best_game.score = 1200;
+        let edited = seal(&save_data).replace("score:1200", "score:9999");
+
+        assert!(open(&edited).is_err());
+    }
+
+    #[test]
+    fn it_does_reject_a_truncated_save() {
+        let sealed = seal(&SaveData::new());
+
+        assert!(open(&sealed[..sealed.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn it_does_open_an_unsealed_unversioned_save() {
+        let unsealed = r#"(check_sum:0,best_game:(id:0,score:0,level:1,lines_cleared:0,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:00:00Z"),history:[])"#;
+
+        assert!(open(unsealed).is_ok());
+    }
+
+    #[test]
+    fn it_does_keep_the_history_of_an_older_backup() {
+        let v1 = r#"(version:1,check_sum:0,best_game:(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z"),history:[(id:12,score:900,level:2,lines_cleared:7,start_time:"2024-10-01T10:00:00Z",end_time:"2024-10-01T10:05:00Z"),(id:14,score:300,level:1,lines_cleared:3,start_time:"2024-10-03T10:00:00Z",end_time:"2024-10-03T10:01:00Z")])"#;
+        let sealed = v1.replacen("check_sum:0", &format!("check_sum:{}", checksum(v1)), 1);
+        let backups = vec![
+            Err(StorageError::NotFound("save.rvrs.20241004000000000".to_string())),
+            Ok(seal(&SaveData::new())[..10].to_string()),
+            Ok(sealed),
+        ];
+
+        let (save_data, history) = first_valid(backups.into_iter()).unwrap();
+        assert_eq!(save_data.best_game.score, 900);
+        assert_eq!(history.iter().map(|game| game.id).collect::<Vec<_>>(), vec![12, 14]);
+    }
+
+    #[test]
+    fn it_does_label_backups_by_time() {
+        assert_eq!(backup_label("save.rvrs.20241003154501123"), "2024-10-03 15:45");
+        assert_eq!(backup_label("save.rvrs.bak"), "save.rvrs.bak");
+    }
+
+    #[test]
+    fn it_does_reject_a_current_save_posing_as_unsealed() {
+        let unversioned = ron::ser::to_string(&SaveData::new())
+            .unwrap()
+            .replace("version:2,", "");
+
+        assert!(!unversioned.contains("version"));
+        assert!(open(&unversioned).is_err());
+    }
+
+    #[test]
+    fn it_does_reject_an_unsealed_versioned_save() {
+        let unsealed = ron::ser::to_string(&SaveData::new()).unwrap();
+
+        assert!(open(&unsealed).is_err());
+    }
+}
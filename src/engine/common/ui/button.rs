@@ -1,8 +1,11 @@
-use crate::engine::events::events::BUTTON_EVENT;
 use raylib::prelude::*;
 
+use super::context::UiContext;
+
+// true on the frame a press that started on the button is released over it, or it is activated while focused
 pub fn button(
     d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
     width: i32,
     height: i32,
     x: i32,
@@ -14,8 +17,7 @@ pub fn button(
     font_color: Color,
     hover_font_color: Color,
     disabled: bool,
-    event: String,
-) {
+) -> bool {
     let button_x = x - width / 2;
     let button_y = y - height / 2;
 
@@ -27,18 +29,11 @@ pub fn button(
         let text_x = button_x + (width - text_width) / 2;
         let text_y = button_y + (height - text_height) / 2;
         d.draw_text(&text, text_x, text_y, font_size, Color::GRAY);
-        return;
+        return false;
     }
 
-    let mouse = d.get_mouse_position();
-    let mouse_x = mouse.x as i32;
-    let mouse_y = mouse.y as i32;
-
-    if mouse_x >= button_x
-        && mouse_x <= button_x + width
-        && mouse_y >= button_y
-        && mouse_y <= button_y + height
-    {
+    let interaction = ui.interact_focusable(button_x, button_y, width, height);
+    if interaction.hovered || interaction.held || interaction.focused {
         d.draw_rectangle(button_x, button_y, width, height, hover_color);
         let text_width = d.measure_text(&text, font_size);
 
@@ -47,9 +42,6 @@ pub fn button(
         let text_x = button_x + (width - text_width) / 2;
         let text_y = button_y + (height - text_height) / 2;
         d.draw_text(&text, text_x, text_y, font_size, hover_font_color);
-        if d.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
-            BUTTON_EVENT.call(event);
-        }
     } else {
         d.draw_rectangle(button_x, button_y, width, height, color);
         let text_width = d.measure_text(&text, font_size);
@@ -59,4 +51,13 @@ pub fn button(
         let text_y = button_y + (height - text_height) / 2;
         d.draw_text(&text, text_x, text_y, font_size, font_color);
     }
+    if interaction.focused {
+        draw_focus(d, button_x, button_y, width, height);
+    }
+    interaction.clicked
+}
+
+// outline around the widget the keyboard or gamepad is on
+pub fn draw_focus(d: &mut RaylibDrawHandle, x: i32, y: i32, width: i32, height: i32) {
+    d.draw_rectangle_lines_ex(rrect(x - 2, y - 2, width + 4, height + 4), 2.0, Color::BLACK);
 }
@@ -1,7 +1,11 @@
-use raylib::{color::Color, ffi::MouseButton, prelude::{RaylibDraw, RaylibDrawHandle}};
+use raylib::{color::Color, prelude::{RaylibDraw, RaylibDrawHandle}};
 
+use super::{button::draw_focus, context::UiContext};
+
+// true when clicked, the caller flips the value
 pub fn check_box(
     d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
     width: i32,
     height: i32,
     x: i32,
@@ -14,28 +18,16 @@ pub fn check_box(
     let button_x = x - width / 2;
     let button_y = y - height / 2;
 
-    let mouse = d.get_mouse_position();
-    let mouse_x = mouse.x as i32;
-    let mouse_y = mouse.y as i32;
-
-    if mouse_x >= button_x
-        && mouse_x <= button_x + width
-        && mouse_y >= button_y
-        && mouse_y <= button_y + height
-    {
-      
+    let interaction = ui.interact_focusable(button_x, button_y, width, height);
+    if interaction.hovered || interaction.held {
         d.draw_rectangle(button_x, button_y, width, height, hover_color);
-
-
-        if d.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
-            return true;
-        }
+    } else if active {
+        d.draw_rectangle(button_x, button_y, width, height, active_color);
     } else {
-        if active {
-            d.draw_rectangle(button_x, button_y, width, height, active_color);
-        } else {
-            d.draw_rectangle(button_x, button_y, width, height, color);
-        }
+        d.draw_rectangle(button_x, button_y, width, height, color);
+    }
+    if interaction.focused {
+        draw_focus(d, button_x, button_y, width, height);
     }
-    false 
+    interaction.clicked
 }
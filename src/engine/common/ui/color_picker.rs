@@ -1,7 +1,10 @@
 use raylib::{color::Color, prelude::RaylibDrawHandle, rgui::RaylibDrawGui};
 
+use super::context::UiContext;
+
 pub fn color_picker(
     d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
     x: i32,
     y: i32,
     width: i32,
@@ -12,6 +15,14 @@ pub fn color_picker(
     let rect_y = y - height / 2;
 
     let bounds = raylib::math::Rectangle::new(rect_x as f32, rect_y as f32, width as f32, height as f32);
-    d.gui_color_picker(bounds, None, color)
+    // raygui reads the mouse itself, so it is locked rather than given the input
+    if ui.input_blocked() {
+        d.gui_lock();
+    }
+    let color = d.gui_color_picker(bounds, None, color);
+    if ui.input_blocked() {
+        d.gui_unlock();
+    }
+    color
 
 }
@@ -0,0 +1,373 @@
+use once_cell::sync::Lazy;
+use raylib::{
+    ffi::{GamepadButton, KeyboardKey, MouseButton},
+    math::Vector2,
+    RaylibHandle,
+};
+use std::sync::Mutex;
+
+// kept between frames so a press can be followed to its release
+pub static UI_CONTEXT: Lazy<Mutex<UiContext>> = Lazy::new(|| Mutex::new(UiContext::new()));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MouseInput {
+    pub position: Vector2,
+    pub pressed: bool,
+    pub down: bool,
+    pub released: bool,
+}
+
+impl MouseInput {
+    pub fn read(rl: &RaylibHandle) -> MouseInput {
+        MouseInput {
+            position: rl.get_mouse_position(),
+            pressed: rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT),
+            down: rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT),
+            released: rl.is_mouse_button_released(MouseButton::MOUSE_BUTTON_LEFT),
+        }
+    }
+}
+
+// the gamepad menus listen to
+const GAMEPAD: i32 = 0;
+
+// keys and gamepad buttons that move the focus between widgets and press the focused one
+const NAV_KEYS: [KeyboardKey; 7] = [
+    KeyboardKey::KEY_DOWN,
+    KeyboardKey::KEY_RIGHT,
+    KeyboardKey::KEY_UP,
+    KeyboardKey::KEY_LEFT,
+    KeyboardKey::KEY_TAB,
+    KeyboardKey::KEY_ENTER,
+    KeyboardKey::KEY_SPACE,
+];
+const NAV_BUTTONS: [GamepadButton; 5] = [
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP,
+    GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT,
+    GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN,
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NavInput {
+    pub next: bool,
+    pub previous: bool,
+    pub activate: bool,
+    // any of the keys or buttons is down, pressed this frame or earlier
+    pub held: bool,
+}
+
+impl NavInput {
+    pub fn read(rl: &RaylibHandle) -> NavInput {
+        let key = |key| rl.is_key_pressed(key);
+        let pad = |button| rl.is_gamepad_available(GAMEPAD) && rl.is_gamepad_button_pressed(GAMEPAD, button);
+        let shift = rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT);
+        NavInput {
+            next: key(KeyboardKey::KEY_DOWN)
+                || key(KeyboardKey::KEY_RIGHT)
+                || (key(KeyboardKey::KEY_TAB) && !shift)
+                || pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN)
+                || pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT),
+            previous: key(KeyboardKey::KEY_UP)
+                || key(KeyboardKey::KEY_LEFT)
+                || (key(KeyboardKey::KEY_TAB) && shift)
+                || pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP)
+                || pad(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT),
+            activate: key(KeyboardKey::KEY_ENTER)
+                || key(KeyboardKey::KEY_SPACE)
+                || pad(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+            held: NAV_KEYS.iter().any(|&key| rl.is_key_down(key))
+                || (rl.is_gamepad_available(GAMEPAD)
+                    && NAV_BUTTONS.iter().any(|&button| rl.is_gamepad_button_down(GAMEPAD, button))),
+        }
+    }
+}
+
+// widgets are told apart by where they are and which panel they are on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidgetId {
+    layer: u32,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Interaction {
+    // the mouse is over the widget and nothing else is being held
+    pub hovered: bool,
+    // the press started on the widget and the button is still down
+    pub held: bool,
+    // the press started on the widget and was released over it, or it was activated while focused
+    pub clicked: bool,
+    pub focused: bool,
+}
+
+pub struct UiContext {
+    mouse: MouseInput,
+    nav: NavInput,
+    // the top widget under the mouse last frame, the only one a press can start on
+    hot: Option<WidgetId>,
+    next_hot: Option<WidgetId>,
+    // the widget the press started on, until the button is released
+    active: Option<WidgetId>,
+    // modal panels, widgets only take input on the top one
+    layer: u32,
+    layers: u32,
+    // known before anything is drawn, so widgets drawn before a panel are blocked too
+    last_layers: u32,
+    // nothing has focus until the keyboard or gamepad moves it
+    focused: Option<WidgetId>,
+    // focusable widgets in the order they were drawn, last frame's is what the focus moves along
+    focus_order: Vec<WidgetId>,
+    last_focus_order: Vec<WidgetId>,
+    // a text field is on screen, so enter and space are left to it
+    typing: bool,
+    last_typing: bool,
+    // a panel opened, the nav keys played with under it have to be let go before it takes them
+    nav_locked: bool,
+}
+
+impl UiContext {
+    pub fn new() -> UiContext {
+        UiContext {
+            mouse: MouseInput::default(),
+            nav: NavInput::default(),
+            hot: None,
+            next_hot: None,
+            active: None,
+            layer: 0,
+            layers: 0,
+            last_layers: 0,
+            focused: None,
+            focus_order: vec![],
+            last_focus_order: vec![],
+            typing: false,
+            last_typing: false,
+            nav_locked: false,
+        }
+    }
+
+    pub fn begin_frame(&mut self, mouse: MouseInput, mut nav: NavInput) {
+        if self.nav_locked && nav.held {
+            nav = NavInput::default();
+        } else {
+            self.nav_locked = false;
+        }
+        self.mouse = mouse;
+        self.nav = nav;
+        self.next_hot = None;
+        self.layer = 0;
+        self.layers = 0;
+        if nav.next != nav.previous {
+            self.focused = step_focus(&self.last_focus_order, self.focused, nav.next);
+        }
+        self.focus_order.clear();
+        self.typing = false;
+    }
+
+    pub fn end_frame(&mut self) {
+        self.hot = self.next_hot;
+        self.last_layers = self.layers;
+        if self.mouse.released || !self.mouse.down {
+            self.active = None;
+        }
+        // the focused widget went away with its screen or under a panel
+        if self.focused.is_some_and(|focused| !self.focus_order.contains(&focused)) {
+            self.focused = None;
+        }
+        self.last_focus_order = std::mem::take(&mut self.focus_order);
+        self.last_typing = self.typing;
+    }
+
+    // widgets between begin_modal and end_modal block the input of everything under them
+    pub fn begin_modal(&mut self) {
+        self.layer += 1;
+        // keys pressed for the game or screen under a panel that just opened aren't meant for it
+        if self.layer > self.last_layers {
+            self.nav = NavInput::default();
+            self.nav_locked = true;
+        }
+        self.layers = self.layers.max(self.layer);
+    }
+
+    pub fn end_modal(&mut self) {
+        self.layer = self.layer.saturating_sub(1);
+    }
+
+    pub fn input_blocked(&self) -> bool {
+        self.layer < self.layers.max(self.last_layers)
+    }
+
+    pub fn mouse(&self) -> MouseInput {
+        self.mouse
+    }
+
+    pub fn start_typing(&mut self) {
+        self.typing = true;
+    }
+
+    // a text field was on screen last frame
+    pub fn was_typing(&self) -> bool {
+        self.last_typing
+    }
+
+    // x and y are the top left corner
+    pub fn interact(&mut self, x: i32, y: i32, width: i32, height: i32) -> Interaction {
+        if self.input_blocked() {
+            return Interaction::default();
+        }
+
+        let id = WidgetId { layer: self.layer, x, y, width, height };
+        let mouse_x = self.mouse.position.x as i32;
+        let mouse_y = self.mouse.position.y as i32;
+        let over = mouse_x >= x && mouse_x <= x + width && mouse_y >= y && mouse_y <= y + height;
+
+        if over {
+            self.next_hot = Some(id);
+        }
+        if self.mouse.pressed && self.active.is_none() && self.hot == Some(id) && over {
+            self.active = Some(id);
+        }
+
+        let held = self.active == Some(id);
+        Interaction {
+            hovered: over && (held || self.active.is_none()),
+            held: held && self.mouse.down,
+            clicked: held && self.mouse.released && over,
+            focused: false,
+        }
+    }
+
+    // like interact, for widgets the keyboard and gamepad can move to and press
+    pub fn interact_focusable(&mut self, x: i32, y: i32, width: i32, height: i32) -> Interaction {
+        let mut interaction = self.interact(x, y, width, height);
+        if self.input_blocked() {
+            return interaction;
+        }
+
+        let id = WidgetId { layer: self.layer, x, y, width, height };
+        self.focus_order.push(id);
+        if self.focused == Some(id) {
+            interaction.focused = true;
+            interaction.clicked |= self.nav.activate && !self.last_typing;
+        }
+        interaction
+    }
+}
+
+// the widget after or before the focused one, wrapping around
+fn step_focus(order: &[WidgetId], focused: Option<WidgetId>, forward: bool) -> Option<WidgetId> {
+    if order.is_empty() {
+        return None;
+    }
+    let len = order.len();
+    let index = match focused.and_then(|focused| order.iter().position(|id| *id == focused)) {
+        Some(index) if forward => (index + 1) % len,
+        Some(index) => (index + len - 1) % len,
+        None if forward => 0,
+        None => len - 1,
+    };
+    Some(order[index])
+}
+
+impl Default for UiContext {
+    fn default() -> Self {
+        UiContext::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame(ui: &mut UiContext, x: f32, pressed: bool, down: bool, released: bool, modal: bool) -> Interaction {
+        ui.begin_frame(
+            MouseInput { position: Vector2::new(x, 10.0), pressed, down, released },
+            NavInput::default(),
+        );
+        let interaction = ui.interact(0, 0, 20, 20);
+        if modal {
+            ui.begin_modal();
+            ui.end_modal();
+        }
+        ui.end_frame();
+        interaction
+    }
+
+    #[test]
+    fn it_does_click_on_release_inside() {
+        let mut ui = UiContext::new();
+        frame(&mut ui, 10.0, false, false, false, false);
+        assert!(frame(&mut ui, 10.0, true, true, false, false).held);
+        assert!(frame(&mut ui, 10.0, false, false, true, false).clicked);
+
+        // pressed elsewhere and slid on
+        frame(&mut ui, 50.0, true, true, false, false);
+        frame(&mut ui, 10.0, false, true, false, false);
+        assert!(!frame(&mut ui, 10.0, false, false, true, false).clicked);
+
+        // pressed on and slid off
+        frame(&mut ui, 10.0, true, true, false, false);
+        assert!(!frame(&mut ui, 50.0, false, false, true, false).clicked);
+    }
+
+    #[test]
+    fn it_does_block_widgets_under_a_modal() {
+        let mut ui = UiContext::new();
+        frame(&mut ui, 10.0, false, false, false, true);
+        assert_eq!(frame(&mut ui, 10.0, true, true, false, true), Interaction::default());
+        assert!(!frame(&mut ui, 10.0, false, false, true, true).clicked);
+    }
+
+    #[test]
+    fn it_does_move_focus_and_activate() {
+        let mut ui = UiContext::new();
+        let nav_frame = |ui: &mut UiContext, nav: NavInput| {
+            ui.begin_frame(MouseInput::default(), nav);
+            let first = ui.interact_focusable(100, 0, 20, 20);
+            let second = ui.interact_focusable(100, 30, 20, 20);
+            ui.end_frame();
+            (first, second)
+        };
+        let next = NavInput { next: true, ..NavInput::default() };
+        let previous = NavInput { previous: true, ..NavInput::default() };
+
+        let (first, second) = nav_frame(&mut ui, NavInput::default());
+        assert!(!first.focused && !second.focused);
+        assert!(nav_frame(&mut ui, next).0.focused);
+        assert!(nav_frame(&mut ui, next).1.focused);
+        // wraps around both ways
+        assert!(nav_frame(&mut ui, next).0.focused);
+        assert!(nav_frame(&mut ui, previous).1.focused);
+        assert!(nav_frame(&mut ui, NavInput { activate: true, ..NavInput::default() }).1.clicked);
+    }
+
+    #[test]
+    fn it_does_ignore_nav_held_into_a_new_modal() {
+        let mut ui = UiContext::new();
+        let modal_frame = |ui: &mut UiContext, nav: NavInput, modal: bool| {
+            ui.begin_frame(MouseInput::default(), nav);
+            let mut interaction = Interaction::default();
+            if modal {
+                ui.begin_modal();
+                interaction = ui.interact_focusable(100, 0, 20, 20);
+                ui.end_modal();
+            }
+            ui.end_frame();
+            interaction
+        };
+        let next = NavInput { next: true, held: true, ..NavInput::default() };
+        let activate = NavInput { activate: true, held: true, ..NavInput::default() };
+
+        modal_frame(&mut ui, next, false);
+        assert!(!modal_frame(&mut ui, next, true).focused);
+        // still down from the game under the panel
+        assert!(!modal_frame(&mut ui, next, true).focused);
+        modal_frame(&mut ui, NavInput::default(), true);
+        assert!(modal_frame(&mut ui, next, true).focused);
+        assert!(modal_frame(&mut ui, activate, true).clicked);
+    }
+}
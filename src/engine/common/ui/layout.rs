@@ -0,0 +1,150 @@
+use raylib::RaylibHandle;
+
+// an area of the window in pixels, x and y are the top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+// where in a rectangle another one is placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    pub fn window(rl: &RaylibHandle) -> Rect {
+        Rect::new(0, 0, rl.get_screen_width(), rl.get_screen_height())
+    }
+
+    // widgets are placed by their center
+    pub fn center_x(&self) -> i32 {
+        self.x + self.width / 2
+    }
+
+    pub fn center_y(&self) -> i32 {
+        self.y + self.height / 2
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    pub fn inset(&self, left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect::new(
+            self.x + left,
+            self.y + top,
+            (self.width - left - right).max(0),
+            (self.height - top - bottom).max(0),
+        )
+    }
+
+    pub fn padded(&self, padding: i32) -> Rect {
+        self.inset(padding, padding, padding, padding)
+    }
+
+    // a rectangle of the given size at the anchor of this one, moved by the offset
+    pub fn anchored(&self, anchor: Anchor, width: i32, height: i32, offset_x: i32, offset_y: i32) -> Rect {
+        let x = match anchor {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => self.x,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => self.center_x() - width / 2,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => self.x + self.width - width,
+        };
+        let y = match anchor {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => self.y,
+            Anchor::Left | Anchor::Center | Anchor::Right => self.center_y() - height / 2,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => self.y + self.height - height,
+        };
+        Rect::new(x + offset_x, y + offset_y, width, height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Vertical,
+    Horizontal,
+}
+
+// hands out rectangles one after another from the start of an area, each centered across it
+pub struct Stack {
+    area: Rect,
+    direction: Direction,
+    spacing: i32,
+    cursor: i32,
+}
+
+impl Stack {
+    pub fn vertical(area: Rect, spacing: i32) -> Stack {
+        Stack { area, direction: Direction::Vertical, spacing, cursor: 0 }
+    }
+
+    pub fn horizontal(area: Rect, spacing: i32) -> Stack {
+        Stack { area, direction: Direction::Horizontal, spacing, cursor: 0 }
+    }
+
+    pub fn next(&mut self, width: i32, height: i32) -> Rect {
+        match self.direction {
+            Direction::Vertical => {
+                let rect = Rect::new(self.area.center_x() - width / 2, self.area.y + self.cursor, width, height);
+                self.cursor += height + self.spacing;
+                rect
+            }
+            Direction::Horizontal => {
+                let rect = Rect::new(self.area.x + self.cursor, self.area.center_y() - height / 2, width, height);
+                self.cursor += width + self.spacing;
+                rect
+            }
+        }
+    }
+
+    // leaves a gap before the next rectangle
+    pub fn skip(&mut self, amount: i32) {
+        self.cursor += amount;
+    }
+}
+
+// how long a stack of equal rectangles is, for sizing or centering the area around them
+pub fn stack_length(count: usize, size: i32, spacing: i32) -> i32 {
+    if count == 0 {
+        return 0;
+    }
+    count as i32 * size + (count as i32 - 1) * spacing
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_place_rectangles_in_a_stack() {
+        let board = Rect::new(600, 50, 160, 640);
+        let panel = board.anchored(Anchor::Center, 160, 130, 0, 0);
+        assert_eq!(panel, Rect::new(600, 305, 160, 130));
+        assert_eq!(board.anchored(Anchor::BottomRight, 20, 10, -5, 0), Rect::new(735, 680, 20, 10));
+
+        let mut column = Stack::vertical(panel.padded(10), 10);
+        assert_eq!(column.next(140, 30), Rect::new(610, 315, 140, 30));
+        column.skip(5);
+        assert_eq!(column.next(100, 30), Rect::new(630, 360, 100, 30));
+
+        let mut row = Stack::horizontal(Rect::new(0, 0, 100, 40), 4);
+        row.next(10, 20);
+        assert_eq!(row.next(10, 20), Rect::new(14, 10, 10, 20));
+        assert_eq!(stack_length(3, 30, 10), 110);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod button;
+pub mod context;
+pub mod layout;
 pub mod rectangle;
 pub mod text;
 pub mod check_box;
-pub mod color_picker;
\ No newline at end of file
+pub mod color_picker;
+pub mod text_field;
+pub mod slider;
\ No newline at end of file
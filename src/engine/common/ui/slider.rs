@@ -0,0 +1,31 @@
+use raylib::{color::Color, prelude::{RaylibDraw, RaylibDrawHandle}};
+
+use super::context::UiContext;
+
+// a bar filled up to value, from 0 to 1, dragging on it moves the value there
+pub fn slider(
+    d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+    color: Color,
+    fill_color: Color,
+    value: f32,
+) -> f32 {
+    let slider_x = x - width / 2;
+    let slider_y = y - height / 2;
+
+    // keeps following the mouse outside the bar until the button is released
+    let mut value = value.clamp(0.0, 1.0);
+    if ui.interact(slider_x, slider_y, width, height).held {
+        let mouse_x = ui.mouse().position.x as i32;
+        value = ((mouse_x - slider_x) as f32 / width as f32).clamp(0.0, 1.0);
+    }
+
+    d.draw_rectangle(slider_x, slider_y, width, height, color);
+    d.draw_rectangle(slider_x, slider_y, (width as f32 * value) as i32, height, fill_color);
+    d.draw_rectangle_lines(slider_x, slider_y, width, height, Color::BLACK);
+    value
+}
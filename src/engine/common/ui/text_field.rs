@@ -0,0 +1,48 @@
+use raylib::{
+    color::Color,
+    ffi::KeyboardKey,
+    prelude::{RaylibDraw, RaylibDrawHandle},
+};
+
+use super::context::UiContext;
+
+// an always focused single line field, returns the value with this frame's typing applied
+// typing is ignored while a modal panel is over the field, enter and space don't press the focused widget while it is shown
+pub fn text_field(
+    d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+    value: &str,
+    font_size: i32,
+    max_length: usize,
+) -> String {
+    let mut value = value.to_string();
+    if !ui.input_blocked() {
+        ui.start_typing();
+        while let Some(c) = d.get_char_pressed() {
+            if !c.is_control() && value.chars().count() < max_length {
+                value.push(c);
+            }
+        }
+        if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+            value.pop();
+        }
+    }
+
+    let field_x = x - width / 2;
+    let field_y = y - height / 2;
+    d.draw_rectangle(field_x, field_y, width, height, Color::WHITE);
+    d.draw_rectangle_lines(field_x, field_y, width, height, Color::BLACK);
+
+    // blinking caret after the text
+    let text_y = y - font_size / 2;
+    d.draw_text(&value, field_x + 6, text_y, font_size, Color::BLACK);
+    if (d.get_time() * 2.0) as i64 % 2 == 0 {
+        let caret_x = field_x + 8 + d.measure_text(&value, font_size);
+        d.draw_line(caret_x, text_y, caret_x, text_y + font_size, Color::BLACK);
+    }
+    value
+}
@@ -1,12 +1,14 @@
 use once_cell::sync::Lazy;
 
-use super::types::{base::BaseEvent, button::StringEvent};
+use super::types::{action::Action, event::Event, gameplay::Gameplay};
 
-pub static UPDATE_EVENT: Lazy<BaseEvent> = Lazy::new(|| BaseEvent::new());
-pub static TICK_EVENT: Lazy<BaseEvent> = Lazy::new(|| BaseEvent::new());
-pub static RENDER_EVENT: Lazy<BaseEvent> = Lazy::new(|| BaseEvent::new());
+pub static UPDATE_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
+pub static TICK_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
+pub static RENDER_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
 
-pub static BUTTON_EVENT: Lazy<StringEvent> = Lazy::new(|| StringEvent::new());
+pub static BUTTON_EVENT: Lazy<Event<Action>> = Lazy::new(Event::new);
 
-pub static START_GAME_EVENT: Lazy<BaseEvent> = Lazy::new(|| BaseEvent::new());
-pub static END_GAME_EVENT: Lazy<BaseEvent> = Lazy::new(|| BaseEvent::new());
+pub static START_GAME_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
+pub static END_GAME_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
+
+pub static GAMEPLAY_EVENT: Lazy<Event<Gameplay>> = Lazy::new(Event::new);
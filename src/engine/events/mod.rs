@@ -1,3 +1,4 @@
 pub mod events;
+pub mod queue;
 
 pub mod types;
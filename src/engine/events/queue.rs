@@ -0,0 +1,47 @@
+use once_cell::sync::Lazy;
+use std::{collections::VecDeque, sync::Mutex};
+
+type Deferred = Box<dyn FnOnce() + Send + 'static>;
+
+// events posted while something else is being handled, run by process_events once per frame
+static EVENT_QUEUE: Lazy<Mutex<VecDeque<Deferred>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+pub fn defer<F: FnOnce() + Send + 'static>(callback: F) {
+    EVENT_QUEUE.lock().unwrap().push_back(Box::new(callback));
+}
+
+// runs everything posted so far, oldest first, along with anything those post in turn
+pub fn process_events() {
+    loop {
+        // popped on its own line so the lock isn't held while the event runs
+        let next = EVENT_QUEUE.lock().unwrap().pop_front();
+        match next {
+            Some(callback) => callback(),
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn it_does_run_posted_events_after_the_current_one() {
+        let order = Arc::new(Mutex::new(vec![]));
+
+        let outer = order.clone();
+        defer(move || {
+            let inner = outer.clone();
+            defer(move || inner.lock().unwrap().push("posted while handling"));
+            outer.lock().unwrap().push("first");
+        });
+        let second = order.clone();
+        defer(move || second.lock().unwrap().push("second"));
+
+        process_events();
+
+        assert_eq!(*order.lock().unwrap(), vec!["first", "second", "posted while handling"]);
+    }
+}
@@ -0,0 +1,155 @@
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use strum::{EnumDiscriminants, EnumIter};
+
+use crate::engine::{
+    common::{export::ExportFormat, storage::history::HistoryColumn},
+    managers::game_manager::{Control, GameMode, MusicIntensity},
+};
+
+// what a button does when it is pressed, ActionKind is the same without what each one carries
+#[derive(Debug, Clone, PartialEq, EnumDiscriminants)]
+#[strum_discriminants(name(ActionKind), derive(Hash, EnumIter))]
+pub enum Action {
+    // menus
+    Play,
+    Puzzles,
+    Leaderboards,
+    Stats,
+    Settings,
+    Profiles,
+    Blocks,
+    Back,
+    MainMenu,
+    Quit,
+    DismissNotice,
+    // games
+    StartGame(GameMode),
+    StartPuzzle(String),
+    Restart,
+    Resume,
+    Finish,
+    // leaderboards
+    LeaderboardMode(GameMode),
+    SubmitEntry,
+    SkipEntry,
+    // stats
+    ScrollStatsUp,
+    ScrollStatsDown,
+    SortStats(HistoryColumn),
+    DeleteGame(i32),
+    // the format and the directory to write it to
+    Export(ExportFormat, String),
+    // settings
+    MusicIntensity(MusicIntensity),
+    RestoreBackup(String),
+    Controls,
+    Rebind(Control),
+    ResetControls,
+    // profiles
+    SelectProfile(String),
+    NewProfile,
+    RenameProfile(String),
+    CancelProfileEdit,
+    SaveProfile,
+    DeleteProfile(String),
+    // blocks
+    SaveShape,
+    // actions added by mods, named under the mod's own url like "com.example.mod.open"
+    Custom(String),
+}
+
+// handlers are found by the kind, or by the name for custom actions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ActionKey {
+    Builtin(ActionKind),
+    Custom(String),
+}
+
+impl Action {
+    pub fn key(&self) -> ActionKey {
+        match self {
+            Action::Custom(name) => ActionKey::Custom(name.clone()),
+            action => ActionKey::Builtin(action.into()),
+        }
+    }
+}
+
+pub type ActionHandler = Arc<dyn Fn(&Action) + Send + Sync>;
+
+// the builtin actions are registered in listeners::actions, mods add theirs by name
+static ACTION_HANDLERS: Lazy<RwLock<HashMap<ActionKey, ActionHandler>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// handles every action of the kind, whatever it carries
+pub fn on_action(kind: ActionKind, handler: impl Fn(&Action) + Send + Sync + 'static) {
+    ACTION_HANDLERS.write().unwrap().insert(ActionKey::Builtin(kind), Arc::new(handler));
+}
+
+pub fn on_custom_action(name: &str, handler: impl Fn(&Action) + Send + Sync + 'static) {
+    ACTION_HANDLERS
+        .write()
+        .unwrap()
+        .insert(ActionKey::Custom(name.to_string()), Arc::new(handler));
+}
+
+// false when nothing handles the action
+pub fn handle_action(action: &Action) -> bool {
+    // cloned out so a handler can register other handlers
+    let handler = ACTION_HANDLERS.read().unwrap().get(&action.key()).cloned();
+    match handler {
+        Some(handler) => {
+            handler(action);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::{listeners::actions::register_actions, managers::lock_globals};
+    use std::sync::Mutex;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn it_does_dispatch_by_kind_and_custom_name() {
+        let _globals = lock_globals();
+        let handled = Arc::new(Mutex::new(vec![]));
+        let modes = handled.clone();
+        on_action(ActionKind::LeaderboardMode, move |action| {
+            modes.lock().unwrap().push(action.clone());
+        });
+        let custom = handled.clone();
+        on_custom_action("com.example.mod.open", move |action| {
+            custom.lock().unwrap().push(action.clone());
+        });
+
+        assert!(handle_action(&Action::LeaderboardMode(GameMode::Zen)));
+        assert!(handle_action(&Action::Custom("com.example.mod.open".to_string())));
+        assert!(!handle_action(&Action::Custom("com.example.mod.close".to_string())));
+
+        assert_eq!(
+            *handled.lock().unwrap(),
+            vec![
+                Action::LeaderboardMode(GameMode::Zen),
+                Action::Custom("com.example.mod.open".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_does_register_every_builtin_action() {
+        let _globals = lock_globals();
+        register_actions();
+
+        let handlers = ACTION_HANDLERS.read().unwrap();
+        for kind in ActionKind::iter().filter(|kind| *kind != ActionKind::Custom) {
+            assert!(handlers.contains_key(&ActionKey::Builtin(kind)), "{:?} has no handler", kind);
+        }
+    }
+}
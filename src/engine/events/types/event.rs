@@ -0,0 +1,176 @@
+use std::{cell::Cell, sync::Arc};
+
+use event_listener_primitives::{Bag, BagOnce, HandlerId};
+
+use crate::engine::events::queue;
+
+// handlers run from High to Low, in no particular order within the same priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 3] = [Priority::High, Priority::Normal, Priority::Low];
+
+    fn index(&self) -> usize {
+        match self {
+            Priority::High => 0,
+            Priority::Normal => 1,
+            Priority::Low => 2,
+        }
+    }
+}
+
+// what a handler wants to happen to the handlers after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    Stop,
+}
+
+// keeps a handler subscribed, dropping it unsubscribes the handler
+#[must_use = "the handler is unsubscribed as soon as the subscription is dropped"]
+pub struct Subscription {
+    _handler: HandlerId,
+}
+
+impl From<HandlerId> for Subscription {
+    fn from(handler: HandlerId) -> Self {
+        Self { _handler: handler }
+    }
+}
+
+type Handler<T> = Arc<dyn Fn(T) -> Propagation + Send + Sync + 'static>;
+
+pub struct Handlers<T: Clone + 'static> {
+    // one bag per priority, in the order of Priority::ALL
+    pub action: [Bag<Handler<T>>; 3],
+    pub closed: BagOnce<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+impl<T: Clone + 'static> Default for Handlers<T> {
+    fn default() -> Self {
+        Self {
+            action: Default::default(),
+            closed: BagOnce::default(),
+        }
+    }
+}
+
+pub struct Event<T: Clone + 'static> {
+    pub handlers: Handlers<T>,
+}
+
+impl<T: Clone + 'static> Drop for Event<T> {
+    fn drop(&mut self) {
+        self.handlers.closed.call_simple();
+    }
+}
+
+impl<T: Clone + 'static> Default for Event<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static> Event<T> {
+    pub fn new() -> Self {
+        let handlers = Handlers::default();
+
+        Self { handlers }
+    }
+
+    // returns Stop if a handler stopped the event before it reached everyone
+    pub fn call(&self, arg: T) -> Propagation {
+        let stopped = Cell::new(false);
+        for priority in Priority::ALL {
+            self.handlers.action[priority.index()].call(|callback| {
+                if !stopped.get() && callback(arg.clone()) == Propagation::Stop {
+                    stopped.set(true);
+                }
+            });
+            if stopped.get() {
+                return Propagation::Stop;
+            }
+        }
+        Propagation::Continue
+    }
+
+    // calls the event once whatever is being handled right now has finished
+    pub fn post(&'static self, arg: T)
+    where
+        T: Send,
+    {
+        queue::defer(move || {
+            self.call(arg);
+        });
+    }
+
+    pub fn on_event<F: Fn(T) + Send + Sync + 'static>(&self, callback: F) -> Subscription {
+        self.on_event_with(Priority::Normal, move |arg| {
+            callback(arg);
+            Propagation::Continue
+        })
+    }
+
+    pub fn on_event_with<F: Fn(T) -> Propagation + Send + Sync + 'static>(
+        &self,
+        priority: Priority,
+        callback: F,
+    ) -> Subscription {
+        self.handlers.action[priority.index()].add(Arc::new(callback)).into()
+    }
+
+    pub fn on_closed<F: FnOnce() + Send + 'static>(&self, callback: F) -> Subscription {
+        self.handlers.closed.add(Box::new(callback)).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn it_does_unsubscribe_on_drop() {
+        let event: Event<i32> = Event::new();
+        let calls = Arc::new(Mutex::new(vec![]));
+
+        let recorder = calls.clone();
+        let subscription = event.on_event(move |arg| recorder.lock().unwrap().push(arg));
+        event.call(1);
+        drop(subscription);
+        event.call(2);
+
+        assert_eq!(*calls.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn it_does_run_by_priority_until_stopped() {
+        let event: Event<i32> = Event::new();
+        let calls = Arc::new(Mutex::new(vec![]));
+
+        let low = calls.clone();
+        let _low = event.on_event_with(Priority::Low, move |arg| {
+            low.lock().unwrap().push(("low", arg));
+            Propagation::Continue
+        });
+        let high = calls.clone();
+        let _high = event.on_event_with(Priority::High, move |arg| {
+            high.lock().unwrap().push(("high", arg));
+            if arg > 1 {
+                Propagation::Stop
+            } else {
+                Propagation::Continue
+            }
+        });
+
+        assert_eq!(event.call(1), Propagation::Continue);
+        assert_eq!(event.call(2), Propagation::Stop);
+        assert_eq!(*calls.lock().unwrap(), vec![("high", 1), ("low", 1), ("high", 2)]);
+    }
+}
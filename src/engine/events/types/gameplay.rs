@@ -0,0 +1,36 @@
+// what just happened to the piece or the board, fired from the tick
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gameplay {
+    PieceSpawned {
+        piece: String,
+    },
+    // moves the player made, gravity pulling the piece down isn't one of them
+    PieceMoved {
+        dx: i32,
+        dy: i32,
+    },
+    PieceRotated {
+        clockwise: bool,
+        // rotation doesn't try other spots when blocked yet, so this is always false for now
+        kicked: bool,
+    },
+    HardDropped {
+        rows: i32,
+    },
+    PieceLocked {
+        spin: bool,
+    },
+    LinesCleared {
+        count: i32,
+        spin: bool,
+        // 0 for the first clear of a combo
+        combo: i32,
+    },
+    Held,
+    LevelUp {
+        level: i32,
+    },
+    TopOut,
+    // a puzzle ran out of pieces before the objective was met
+    OutOfPieces,
+}
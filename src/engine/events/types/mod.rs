@@ -1,2 +1,3 @@
-pub mod base;
-pub mod button;
+pub mod action;
+pub mod event;
+pub mod gameplay;
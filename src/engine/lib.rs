@@ -1,8 +1,21 @@
 use crate::engine::managers::game_manager::{
-    read_game_manager, write_game_manager_save_data, write_game_manager_should_quit,
+    default_pieces, read_game_manager, write_game_manager_notice, write_game_manager_pieces,
+    write_game_manager_profiles, write_game_manager_save_data, write_game_manager_save_locked,
+    write_game_manager_should_quit, ProfilesView,
 };
 
-use super::{common::storage, events::events::UPDATE_EVENT, listeners::lib::register_events};
+use super::{
+    audio,
+    common::{
+        navigation,
+        storage::{
+            history, profile,
+            save_file::{self, LoadOutcome},
+        },
+    },
+    events::{events::UPDATE_EVENT, queue::process_events},
+    listeners::lib::register_events,
+};
 use lazy_static::lazy_static;
 use std::sync::{Arc, Mutex};
 
@@ -40,28 +53,19 @@ pub fn start() {
         }
     }
 
-    // load save data from file
-    let save_data = storage::lib::load("save.rvrs");
-
-    // if empty, create new save data
-    if save_data.is_empty() {
-        // save it as the savedata struct
-        // SaveData creation
-        let save_data = crate::engine::managers::game_manager::SaveData::new();
-        let serialized_save_data = ron::ser::to_string(&save_data).unwrap();
-
-        // Debug the serialized form
-        println!("Serialized SaveData: {:?}", serialized_save_data);
+    audio::lib::init();
 
-        // Save the serialized data
-        storage::lib::save("save.rvrs", &serialized_save_data);
-    } else {
-        let save_data: crate::engine::managers::game_manager::SaveData =
-            ron::de::from_str(&save_data).unwrap();
-
-        write_game_manager_save_data(save_data);
+    // everyone picks their profile first
+    let mut profiles = ProfilesView::default();
+    match profile::load_index() {
+        Ok(index) => profiles.index = index,
+        Err(e) => {
+            let notice = format!("The profile list could not be read because {}.", e);
+            write_game_manager_notice(Some(notice));
+        }
     }
-    println!("{:#?}", read_game_manager().save_data);
+    write_game_manager_profiles(profiles);
+    navigation::reset("profiles");
 
     while !read_game_manager().should_quit {
         let should_quit = {
@@ -75,7 +79,9 @@ pub fn start() {
 
         write_game_manager_should_quit(read_game_manager().should_quit || should_quit);
 
-        UPDATE_EVENT.call();
+        UPDATE_EVENT.call(());
+        // anything posted during the update runs here, before the next frame
+        process_events();
     }
 
     {
@@ -83,3 +89,53 @@ pub fn start() {
         *state = None;
     }
 }
+
+// makes the profile the active one and loads its save, recovering it if it was damaged
+pub fn open_profile(id: &str) {
+    profile::set_active(Some(id.to_string()));
+    write_game_manager_save_locked(false);
+    write_game_manager_notice(None);
+
+    match save_file::load() {
+        LoadOutcome::New(save_data) | LoadOutcome::Loaded(save_data) => {
+            write_game_manager_save_data(save_data);
+        }
+        LoadOutcome::Recovered(save_data, notice) => {
+            write_game_manager_save_data(save_data);
+            write_game_manager_notice(Some(notice));
+        }
+        LoadOutcome::Unavailable(save_data, notice) => {
+            write_game_manager_save_data(save_data);
+            write_game_manager_save_locked(true);
+            write_game_manager_notice(Some(notice));
+        }
+    }
+
+    // clean up the history log if a crash left part of a game in it
+    if !read_game_manager().save_locked {
+        if let Err(e) = history::compact() {
+            write_game_manager_notice(Some(format!("The game history could not be cleaned up because {}.", e)));
+        }
+    }
+
+    load_pieces();
+
+    let mut profiles = read_game_manager().profiles.clone();
+    profiles.index.last_used = Some(id.to_string());
+    profiles.editing = None;
+    profiles.pending_delete = None;
+    if let Err(e) = profile::save_index(&profiles.index) {
+        write_game_manager_notice(Some(format!("The profile list could not be saved because {}.", e)));
+    }
+    write_game_manager_profiles(profiles);
+    navigation::reset("main");
+}
+
+// the default pieces and the blocks the save has made in the block editor
+pub fn load_pieces() {
+    let mut pieces = default_pieces();
+    for block in read_game_manager().save_data.custom_blocks.iter() {
+        pieces.push(block.to_block());
+    }
+    write_game_manager_pieces(pieces);
+}
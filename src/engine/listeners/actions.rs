@@ -0,0 +1,399 @@
+use std::path::PathBuf;
+
+use crate::engine::{
+    common::{
+        export::{default_export_dir, export, ExportFormat},
+        leaderboard, navigation,
+        puzzle::{load_puzzles, PuzzleBlock},
+        storage::{
+            history::{self, HistoryColumn, HistoryQuery},
+            profile, save_file,
+        },
+    },
+    events::{
+        events::{END_GAME_EVENT, START_GAME_EVENT},
+        types::action::{on_action, Action, ActionKind},
+    },
+    lib::{load_pieces, open_profile},
+    managers::{
+        game_manager::{
+            self, read_game_manager, write_game_manager, write_game_manager_backups,
+            write_game_manager_custom_block, write_game_manager_in_game,
+            write_game_manager_leaderboard_mode, write_game_manager_mode, write_game_manager_notice,
+            write_game_manager_pending_entry, write_game_manager_profiles, write_game_manager_puzzle,
+            write_game_manager_puzzles, write_game_manager_rebinding, write_game_manager_running,
+            write_game_manager_save_data, write_game_manager_should_quit, write_game_manager_stats,
+            Block, Control, GameMode, MusicIntensity, ProfileEdit, StatsView,
+        },
+        game_state::read_game_state,
+        game_statics::read_game_statics,
+    },
+};
+
+use super::{
+    base::render::screens::{rebind_screen::REBIND_SCREEN, stats_screen::STATS_ROWS},
+    lib::{unregister_group, GAME_LISTENERS},
+};
+
+// what each button action does, mods add their own with on_custom_action
+pub fn register_actions() {
+    on_action(ActionKind::Play, |_| navigation::push("mode_select"));
+    on_action(ActionKind::Puzzles, |_| open_puzzles());
+    on_action(ActionKind::Leaderboards, |_| navigation::push("leaderboard"));
+    on_action(ActionKind::Stats, |_| open_stats());
+    on_action(ActionKind::Settings, |_| navigation::push("settings"));
+    on_action(ActionKind::Profiles, |_| navigation::push("profiles"));
+    on_action(ActionKind::Blocks, |_| navigation::push("create_shape"));
+    on_action(ActionKind::Back, |_| {
+        navigation::pop();
+    });
+    on_action(ActionKind::MainMenu, |_| {
+        if read_game_manager().in_game && !read_game_state().game_over {
+            END_GAME_EVENT.post(());
+        }
+        write_game_manager_running(false);
+        write_game_manager_in_game(false);
+        unregister_group(GAME_LISTENERS);
+        navigation::reset("main");
+    });
+    on_action(ActionKind::Quit, |_| {
+        if read_game_manager().in_game {
+            END_GAME_EVENT.post(());
+        }
+        write_game_manager_should_quit(true);
+    });
+    on_action(ActionKind::DismissNotice, |_| write_game_manager_notice(None));
+    on_action(ActionKind::StartGame, |action| {
+        if let Action::StartGame(mode) = action {
+            write_game_manager_mode(*mode);
+            START_GAME_EVENT.post(());
+        }
+    });
+    on_action(ActionKind::StartPuzzle, |action| {
+        if let Action::StartPuzzle(id) = action {
+            start_puzzle(id);
+        }
+    });
+    on_action(ActionKind::Restart, |_| {
+        write_game_manager_running(false);
+        START_GAME_EVENT.post(());
+    });
+    on_action(ActionKind::Resume, |_| write_game_manager_running(true));
+    on_action(ActionKind::Finish, |_| {
+        // ends a session that can't top out, showing the game over panel
+        END_GAME_EVENT.post(());
+        write_game_manager_running(true);
+    });
+    on_action(ActionKind::LeaderboardMode, |action| {
+        if let Action::LeaderboardMode(mode) = action {
+            write_game_manager_leaderboard_mode(*mode);
+        }
+    });
+    on_action(ActionKind::SubmitEntry, |_| submit_entry());
+    on_action(ActionKind::SkipEntry, |_| write_game_manager_pending_entry(None));
+    on_action(ActionKind::ScrollStatsUp, |_| {
+        let mut stats = read_game_manager().stats.clone();
+        stats.scroll = stats.scroll.saturating_sub(STATS_ROWS);
+        write_game_manager_stats(stats);
+    });
+    on_action(ActionKind::ScrollStatsDown, |_| {
+        let mut stats = read_game_manager().stats.clone();
+        let last_page = stats.games.len().saturating_sub(STATS_ROWS);
+        stats.scroll = (stats.scroll + STATS_ROWS).min(last_page);
+        write_game_manager_stats(stats);
+    });
+    on_action(ActionKind::SortStats, |action| {
+        if let Action::SortStats(column) = action {
+            sort_stats(*column);
+        }
+    });
+    on_action(ActionKind::DeleteGame, |action| {
+        if let Action::DeleteGame(id) = action {
+            delete_game(*id);
+        }
+    });
+    on_action(ActionKind::Export, |action| {
+        if let Action::Export(format, dir) = action {
+            export_history(*format, dir);
+        }
+    });
+    on_action(ActionKind::MusicIntensity, |action| {
+        if let Action::MusicIntensity(intensity) = action {
+            set_music_intensity(*intensity);
+        }
+    });
+    on_action(ActionKind::RestoreBackup, |action| {
+        if let Action::RestoreBackup(backup) = action {
+            restore_backup(backup);
+        }
+    });
+    on_action(ActionKind::Controls, |_| navigation::push("controls"));
+    // the controls screen picks up the key the dialog returns
+    on_action(ActionKind::Rebind, |action| {
+        if let Action::Rebind(control) = action {
+            rebind(*control);
+        }
+    });
+    on_action(ActionKind::ResetControls, |_| {
+        let mut save_data = read_game_manager().save_data.clone();
+        save_data.settings.key_bindings.clear();
+        write_game_manager_save_data(save_data);
+    });
+    on_action(ActionKind::SelectProfile, |action| {
+        if let Action::SelectProfile(id) = action {
+            if read_game_manager().profiles.index.find(id).is_some() {
+                open_profile(id);
+            }
+        }
+    });
+    on_action(ActionKind::NewProfile, |_| {
+        let mut profiles = read_game_manager().profiles.clone();
+        profiles.editing = Some(ProfileEdit { id: None, name: "".to_string() });
+        write_game_manager_profiles(profiles);
+    });
+    on_action(ActionKind::RenameProfile, |action| {
+        if let Action::RenameProfile(id) = action {
+            rename_profile(id);
+        }
+    });
+    on_action(ActionKind::CancelProfileEdit, |_| {
+        let mut profiles = read_game_manager().profiles.clone();
+        profiles.editing = None;
+        write_game_manager_profiles(profiles);
+    });
+    on_action(ActionKind::SaveProfile, |_| save_profile());
+    on_action(ActionKind::DeleteProfile, |action| {
+        if let Action::DeleteProfile(id) = action {
+            delete_profile(id);
+        }
+    });
+    on_action(ActionKind::SaveShape, |_| save_shape());
+}
+
+fn open_puzzles() {
+    match load_puzzles(&read_game_manager().pieces) {
+        Ok((puzzles, problems)) => {
+            write_game_manager_puzzles(puzzles);
+            if !problems.is_empty() {
+                let problems: Vec<String> =
+                    problems.iter().map(|(file, e)| format!("{} ({})", file, e)).collect();
+                write_game_manager_notice(Some(format!(
+                    "Some puzzles could not be loaded: {}.",
+                    problems.join(", ")
+                )));
+            }
+        }
+        Err(e) => {
+            write_game_manager_puzzles(vec![]);
+            write_game_manager_notice(Some(format!("The puzzles could not be listed because {}.", e)));
+        }
+    }
+    navigation::push("puzzle_select");
+}
+
+fn start_puzzle(id: &str) {
+    let Some(selected) = read_game_manager().puzzles.iter().find(|p| p.id == id).cloned() else {
+        return;
+    };
+    // the pieces can change after the list is loaded, when another profile brings its own blocks
+    if let Err(e) = selected.build_queue(&read_game_manager().pieces) {
+        write_game_manager_notice(Some(format!("The puzzle {} could not be started because {}.", selected.name, e)));
+        return;
+    }
+    write_game_manager_puzzle(Some(selected));
+    write_game_manager_mode(GameMode::Puzzle);
+    START_GAME_EVENT.post(());
+}
+
+fn submit_entry() {
+    let Some(mut entry) = read_game_manager().pending_entry.clone() else {
+        return;
+    };
+    entry.name = entry.name.trim().to_string();
+    if entry.name.is_empty() {
+        return;
+    }
+    let mut save_data = read_game_manager().save_data.clone();
+    save_data.player_name = entry.name.clone();
+    write_game_manager_leaderboard_mode(entry.mode);
+    leaderboard::insert(&mut save_data.leaderboards, entry, read_game_statics().leaderboard_size);
+    write_game_manager_save_data(save_data);
+    write_game_manager_pending_entry(None);
+    if !read_game_manager().save_locked {
+        if let Err(e) = save_file::save(&read_game_manager().save_data) {
+            write_game_manager_notice(Some(format!("Your leaderboard entry could not be saved because {}.", e)));
+        }
+    }
+}
+
+fn open_stats() {
+    match history::query(HistoryQuery::default()) {
+        Ok(games) => {
+            let mut stats = StatsView::new();
+            stats.games = games.collect();
+            stats.export_dir = default_export_dir()
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default();
+            history::sort_games(&mut stats.games, stats.column, stats.descending);
+            write_game_manager_stats(stats);
+            navigation::push("stats");
+        }
+        Err(e) => {
+            write_game_manager_notice(Some(format!("Your game history could not be read because {}.", e)));
+        }
+    }
+}
+
+// an empty directory falls back to the default one, like leaving out --export-dir
+fn export_history(format: ExportFormat, dir: &str) {
+    let dir = match dir.trim() {
+        "" => default_export_dir(),
+        dir => Ok(PathBuf::from(dir)),
+    };
+    let exported = dir.and_then(|dir| export(format, &dir, &read_game_manager().save_data.totals));
+    let notice = match exported {
+        Ok(path) => format!("Exported history to {}", path.to_string_lossy()),
+        Err(e) => format!("The history could not be exported because {}.", e),
+    };
+    write_game_manager_notice(Some(notice));
+}
+
+fn sort_stats(column: HistoryColumn) {
+    let mut stats = read_game_manager().stats.clone();
+    // pressing the sorted column again flips the order
+    stats.descending = if stats.column == column { !stats.descending } else { true };
+    stats.column = column;
+    stats.scroll = 0;
+    history::sort_games(&mut stats.games, stats.column, stats.descending);
+    write_game_manager_stats(stats);
+}
+
+fn delete_game(id: i32) {
+    let mut stats = read_game_manager().stats.clone();
+    if stats.pending_delete != Some(id) {
+        stats.pending_delete = Some(id);
+        write_game_manager_stats(stats);
+        return;
+    }
+
+    let mut save_data = read_game_manager().save_data.clone();
+    let deleted = history::delete(id)
+        .and_then(|_| history::rebuild_summary(&mut save_data))
+        .and_then(|_| save_file::save(&save_data));
+    match deleted {
+        Ok(()) => {
+            stats.games.retain(|game| game.id != id);
+            stats.scroll = stats.scroll.min(stats.games.len().saturating_sub(STATS_ROWS));
+            write_game_manager_save_data(save_data);
+        }
+        Err(e) => {
+            write_game_manager_notice(Some(format!("The game could not be deleted because {}.", e)));
+        }
+    }
+    stats.pending_delete = None;
+    write_game_manager_stats(stats);
+}
+
+fn set_music_intensity(intensity: MusicIntensity) {
+    let mut save_data = read_game_manager().save_data.clone();
+    save_data.settings.music_intensity = intensity;
+    write_game_manager_save_data(save_data);
+}
+
+fn restore_backup(backup: &str) {
+    if read_game_manager().save_locked {
+        return;
+    }
+    match save_file::restore_backup(backup) {
+        Ok(save_data) => {
+            write_game_manager_save_data(save_data);
+            load_pieces();
+            write_game_manager_backups(save_file::list_backups().unwrap_or_default());
+            write_game_manager_notice(Some(format!(
+                "Your save was restored from the backup of {}.",
+                save_file::backup_label(backup)
+            )));
+        }
+        Err(e) => {
+            write_game_manager_notice(Some(format!("The backup could not be restored because {}.", e)));
+        }
+    }
+}
+
+fn rebind(control: Control) {
+    write_game_manager_rebinding(Some(control));
+    navigation::push(REBIND_SCREEN);
+}
+
+fn rename_profile(id: &str) {
+    let mut profiles = read_game_manager().profiles.clone();
+    if let Some(profile) = profiles.index.find(id) {
+        profiles.editing = Some(ProfileEdit { id: Some(profile.id.clone()), name: profile.name.clone() });
+        write_game_manager_profiles(profiles);
+    }
+}
+
+fn save_profile() {
+    let mut profiles = read_game_manager().profiles.clone();
+    let Some(edit) = profiles.editing.take() else {
+        return;
+    };
+    let name = edit.name.trim();
+    if name.is_empty() || profiles.index.name_taken(name, edit.id.as_deref()) {
+        return;
+    }
+    let opened = match edit.id {
+        Some(id) => {
+            profiles.index.rename(&id, name);
+            None
+        }
+        None => Some(profiles.index.create(name).id),
+    };
+    if let Err(e) = profile::save_index(&profiles.index) {
+        write_game_manager_notice(Some(format!("The profile list could not be saved because {}.", e)));
+    }
+    write_game_manager_profiles(profiles);
+    // a new profile is started straight away
+    if let Some(id) = opened {
+        open_profile(&id);
+    }
+}
+
+fn delete_profile(id: &str) {
+    let id = id.to_string();
+    let mut profiles = read_game_manager().profiles.clone();
+    // the profile being played can't be deleted out from under itself
+    if profile::active().as_ref() == Some(&id) {
+        return;
+    }
+    if profiles.pending_delete.as_ref() != Some(&id) {
+        profiles.pending_delete = Some(id);
+        write_game_manager_profiles(profiles);
+        return;
+    }
+
+    profiles.pending_delete = None;
+    let deleted = profile::delete_files(&id).and_then(|_| {
+        profiles.index.remove(&id);
+        profile::save_index(&profiles.index)
+    });
+    if let Err(e) = deleted {
+        write_game_manager_notice(Some(format!("The profile could not be deleted because {}.", e)));
+    }
+    write_game_manager_profiles(profiles);
+}
+
+fn save_shape() {
+    let mut game_manager = game_manager::read_game_manager_only();
+    game_manager.pieces.push(game_manager.custom_block.clone());
+    game_manager.save_data.custom_blocks.push(PuzzleBlock::from_block(&game_manager.custom_block));
+    write_game_manager_custom_block(Block::default());
+    write_game_manager(game_manager);
+    // custom blocks belong to the profile, so they are kept in its save
+    if !read_game_manager().save_locked {
+        if let Err(e) = save_file::save(&read_game_manager().save_data) {
+            write_game_manager_notice(Some(format!("Your block could not be saved because {}.", e)));
+        }
+    }
+    navigation::pop();
+}
@@ -1,72 +1,143 @@
-use raylib::{color::Color, prelude::RaylibDrawHandle};
+use raylib::{color::Color, ffi::KeyboardKey, prelude::RaylibDrawHandle};
 
-use crate::engine::{common::ui, listeners::base::render::render::scaled_value, managers::game_statics::read_game_statics};
+use crate::engine::{
+    common::{
+        leaderboard,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{components::menu_panel_component::render_menu_panel, render::scaled_value},
+    managers::{
+        game_manager::{read_game_manager, write_game_manager_pending_entry, GameMode},
+        game_state::read_game_state,
+        game_statics::read_game_statics,
+    },
+};
 
-pub fn render_game_over(d: &mut RaylibDrawHandle, scale_x: f32, scale_y: f32) {
+pub fn render_game_over(d: &mut RaylibDrawHandle, ui: &mut UiContext, board: Rect, scale_x: f32, scale_y: f32) {
+    if read_game_manager().pending_entry.is_some() {
+        render_name_entry(d, ui, board, scale_x, scale_y);
+        return;
+    }
+
+    let title = match read_game_state().game_data.mode {
+        GameMode::Dig if read_game_state().garbage_lines_remaining <= 0 => "Cleared!",
+        GameMode::Puzzle if read_game_state().objective_complete => "Solved!",
+        GameMode::Puzzle => "Failed",
+        _ => "Game Over",
+    };
+    let buttons = [
+        ("Restart", Action::Restart),
+        ("Main Menu", Action::MainMenu),
+        ("Quit", Action::Quit),
+    ];
+    render_menu_panel(d, ui, board, title, &buttons, scale_x, scale_y);
+}
+
+// asks for a name when the run made its mode's leaderboard
+fn render_name_entry(d: &mut RaylibDrawHandle, ui: &mut UiContext, board: Rect, scale_x: f32, scale_y: f32) {
+    let Some(mut entry) = read_game_manager().pending_entry.clone() else {
+        return;
+    };
+    let rank = leaderboard::rank(
+        &read_game_manager().save_data.leaderboards,
+        &entry,
+        read_game_statics().leaderboard_size,
+    )
+    .unwrap_or(0);
+
+    let panel = board.anchored(Anchor::Center, scaled_value(220, scale_x), scaled_value(200, scale_y), 0, 0);
     ui::rectangle::rectangle(
         d,
-        scaled_value(480, scale_x),
-        scaled_value(335, scale_y),
-        scaled_value(160, scale_x),
-        scaled_value(160, scale_y),
+        panel.center_x(),
+        panel.center_y(),
+        panel.width,
+        panel.height,
         Color::WHITE,
         Color::BLACK,
     );
 
+    let mut column = Stack::vertical(panel.padded(scaled_value(10, scale_y)), scaled_value(10, scale_y));
+    let title = column.next(panel.width, scaled_value(20, scale_y));
     ui::text::text(
         d,
-        scaled_value(480, scale_x),
-        scaled_value(275, scale_y),
+        title.center_x(),
+        title.center_y(),
         Color::BLACK,
-        "Game Over".to_string(),
-        scaled_value(20, scale_y),
+        format!("New Record! #{}", rank + 1),
+        title.height,
     );
 
-    ui::button::button(
+    let prompt = column.next(panel.width, scaled_value(16, scale_y));
+    ui::text::text(
         d,
-        scaled_value(140, scale_x),
-        scaled_value(30, scale_y),
-        scaled_value(480, scale_x),
-        scaled_value(310, scale_y),
-        Color::GRAY,
-        Color::LIGHTBLUE,
-        "Restart".to_string(),
-        scaled_value(20, scale_y),
-        Color::BLACK,
+        prompt.center_x(),
+        prompt.center_y(),
         Color::BLACK,
-        false,
-        format!("{}.{}", read_game_statics().url, "button.restart".to_string()),
+        "Enter your name".to_string(),
+        prompt.height,
+    );
+
+    let field = column.next(scaled_value(180, scale_x), scaled_value(30, scale_y));
+    let name = ui::text_field::text_field(
+        d,
+        ui,
+        field.width,
+        field.height,
+        field.center_x(),
+        field.center_y(),
+        &entry.name,
+        scaled_value(20, scale_y),
+        read_game_statics().player_name_length,
     );
+    if name != entry.name {
+        entry.name = name;
+        write_game_manager_pending_entry(Some(entry.clone()));
+    }
 
-    ui::button::button(
+    let save = column.next(scaled_value(140, scale_x), scaled_value(30, scale_y));
+    if ui::button::button(
         d,
-        scaled_value(140, scale_x),
-        scaled_value(30, scale_y),
-        scaled_value(480, scale_x),
-        scaled_value(350, scale_y),
+        ui,
+        save.width,
+        save.height,
+        save.center_x(),
+        save.center_y(),
         Color::GRAY,
         Color::LIGHTBLUE,
-        "Main Menu".to_string(),
+        "Save".to_string(),
         scaled_value(20, scale_y),
         Color::BLACK,
         Color::BLACK,
-        false,
-        format!("{}.{}", read_game_statics().url, "button.main_menu".to_string()),
-    );
+        entry.name.trim().is_empty(),
+    ) {
+        BUTTON_EVENT.post(Action::SubmitEntry);
+    }
 
-    ui::button::button(
+    let skip = column.next(scaled_value(140, scale_x), scaled_value(30, scale_y));
+    if ui::button::button(
         d,
-        scaled_value(140, scale_x),
-        scaled_value(30, scale_y),
-        scaled_value(480, scale_x),
-        scaled_value(390, scale_y),
+        ui,
+        skip.width,
+        skip.height,
+        skip.center_x(),
+        skip.center_y(),
         Color::GRAY,
         Color::LIGHTBLUE,
-        "Quit".to_string(),
+        "Skip".to_string(),
         scaled_value(20, scale_y),
         Color::BLACK,
         Color::BLACK,
         false,
-        format!("{}.{}", read_game_statics().url, "button.quit".to_string()),
-    );
+    ) {
+        BUTTON_EVENT.post(Action::SkipEntry);
+    }
+
+    if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+        BUTTON_EVENT.post(Action::SubmitEntry);
+    }
 }
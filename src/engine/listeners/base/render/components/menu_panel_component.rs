@@ -0,0 +1,77 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::ui::{
+        self,
+        context::UiContext,
+        layout::{stack_length, Anchor, Rect, Stack},
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::render::scaled_value,
+};
+
+const PADDING: i32 = 10;
+const SPACING: i32 = 10;
+const TITLE_SIZE: i32 = 20;
+const BUTTON_WIDTH: i32 = 140;
+const BUTTON_HEIGHT: i32 = 30;
+
+// a titled column of buttons over the board, sized to fit them
+pub fn render_menu_panel(
+    d: &mut RaylibDrawHandle,
+    ui: &mut UiContext,
+    board: Rect,
+    title: &str,
+    buttons: &[(&str, Action)],
+    scale_x: f32,
+    scale_y: f32,
+) {
+    let padding = scaled_value(PADDING, scale_y);
+    let spacing = scaled_value(SPACING, scale_y);
+    let title_size = scaled_value(TITLE_SIZE, scale_y);
+    let button_height = scaled_value(BUTTON_HEIGHT, scale_y);
+
+    let height = padding * 2 + title_size + spacing + stack_length(buttons.len(), button_height, spacing);
+    let panel = board.anchored(Anchor::Center, scaled_value(BUTTON_WIDTH + PADDING * 2, scale_x), height, 0, 0);
+    ui::rectangle::rectangle(
+        d,
+        panel.center_x(),
+        panel.center_y(),
+        panel.width,
+        panel.height,
+        Color::WHITE,
+        Color::BLACK,
+    );
+
+    let mut column = Stack::vertical(panel.padded(padding), spacing);
+    let title_rect = column.next(panel.width, title_size);
+    ui::text::text(
+        d,
+        title_rect.center_x(),
+        title_rect.center_y(),
+        Color::BLACK,
+        title.to_string(),
+        title_size,
+    );
+
+    for (label, action) in buttons {
+        let rect = column.next(scaled_value(BUTTON_WIDTH, scale_x), button_height);
+        if ui::button::button(
+            d,
+            ui,
+            rect.width,
+            rect.height,
+            rect.center_x(),
+            rect.center_y(),
+            Color::GRAY,
+            Color::LIGHTBLUE,
+            label.to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            false,
+        ) {
+            BUTTON_EVENT.post(action.clone());
+        }
+    }
+}
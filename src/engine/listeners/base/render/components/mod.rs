@@ -1,2 +1,3 @@
 pub mod game_over_component;
+pub mod menu_panel_component;
 pub mod pause_component;
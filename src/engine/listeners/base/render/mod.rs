@@ -1,3 +1,4 @@
 pub mod components;
 pub mod render;
+pub mod screen;
 pub mod screens;
@@ -1,30 +1,62 @@
 use raylib::prelude::*;
+use std::sync::Arc;
 
-use crate::engine::managers::game_manager::read_game_manager;
+use crate::engine::{
+    common::ui::context::{MouseInput, NavInput, UI_CONTEXT},
+    lib::RAYLIB_STATE,
+    managers::{game_manager::read_game_manager, game_statics::read_game_statics},
+};
 
-use super::screens::create_shape_screen::render_create_shape;
-use super::screens::game_screen::render_game;
-use super::screens::main_screen::render_main_menu;
-use super::screens::unknown_screen::render_unknown_screen;
+use super::screen::{get_screen, register_screen, show_screen, Screen};
+use super::screens::controls_screen::ControlsScreen;
+use super::screens::create_shape_screen::CreateShapeScreen;
+use super::screens::game_screen::GameScreen;
+use super::screens::leaderboard_screen::LeaderboardScreen;
+use super::screens::main_screen::MainScreen;
+use super::screens::mode_select_screen::ModeSelectScreen;
+use super::screens::profiles_screen::ProfilesScreen;
+use super::screens::puzzle_select_screen::PuzzleSelectScreen;
+use super::screens::rebind_screen::{RebindScreen, REBIND_SCREEN};
+use super::screens::settings_screen::SettingsScreen;
+use super::screens::stats_screen::StatsScreen;
+use super::screens::unknown_screen::UnknownScreen;
 
 // Base resolution as reference
 const BASE_WIDTH: i32 = 1600;
 const BASE_HEIGHT: i32 = 900;
 
+pub fn register_screens() {
+    register_screen("game", Arc::new(GameScreen));
+    register_screen("main", Arc::new(MainScreen));
+    register_screen("create_shape", Arc::new(CreateShapeScreen));
+    register_screen("mode_select", Arc::new(ModeSelectScreen));
+    register_screen("puzzle_select", Arc::new(PuzzleSelectScreen));
+    register_screen("stats", Arc::new(StatsScreen));
+    register_screen("leaderboard", Arc::new(LeaderboardScreen));
+    register_screen("profiles", Arc::new(ProfilesScreen));
+    register_screen("settings", Arc::new(SettingsScreen));
+    register_screen("controls", Arc::new(ControlsScreen));
+    register_screen(REBIND_SCREEN, Arc::new(RebindScreen));
+}
+
 pub fn on_render() {
-    match read_game_manager().screen.as_str() {
-        "game" => {
-            render_game();
-        }
-        "main" => {
-            render_main_menu();
-        }
-        "create_shape" => {
-            render_create_shape();
-        }
-        _ => {
-            render_unknown_screen();
-        }
+    let id = read_game_manager().screen.clone();
+    let screen: Arc<dyn Screen> = get_screen(&id).unwrap_or_else(|| Arc::new(UnknownScreen));
+    show_screen(&id, &screen);
+    screen.update();
+
+    let mut state = RAYLIB_STATE.lock().unwrap();
+    if let Some(ref mut raylib_state) = *state {
+        screen.handle_input(&mut raylib_state.rl);
+
+        let mut ui = UI_CONTEXT.lock().unwrap();
+        ui.begin_frame(MouseInput::read(&raylib_state.rl), NavInput::read(&raylib_state.rl));
+
+        let mut d = raylib_state.rl.begin_drawing(&raylib_state.thread);
+        d.clear_background(screen.background());
+        let (scale_x, scale_y) = get_scaling_factors(&d);
+        screen.draw(&mut d, &mut ui, scale_x, scale_y);
+        ui.end_frame();
     }
 }
 
@@ -41,3 +73,9 @@ pub fn get_scaling_factors(d: &RaylibDrawHandle) -> (f32, f32) {
 pub fn scaled_value(value: i32, scale: f32) -> i32 {
     (value as f32 * scale).round() as i32
 }
+
+// formats a tick count as m:ss.cc using the game tick rate
+pub fn format_ticks(ticks: i32) -> String {
+    let centis = ticks as i64 * 100 / read_game_statics().tick_rate as i64;
+    format!("{}:{:02}.{:02}", centis / 6000, centis / 100 % 60, centis % 100)
+}
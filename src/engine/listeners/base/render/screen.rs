@@ -0,0 +1,109 @@
+use once_cell::sync::Lazy;
+use raylib::prelude::*;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::engine::{
+    common::ui::context::UiContext,
+    listeners::lib::{replace_group, ListenerGroup, SCREEN_LISTENERS},
+};
+
+// a screen only draws itself, on_render begins the drawing and clears the background for it
+pub trait Screen: Send + Sync {
+    // called on the first frame the screen is shown and the first frame after it is left
+    fn on_enter(&self) {}
+    fn on_exit(&self) {}
+    // handlers that only run while the screen is shown, unsubscribed when it is left
+    fn listeners(&self, _group: &mut ListenerGroup) {}
+    // called every frame before input is read and the screen is drawn
+    fn update(&self) {}
+    fn handle_input(&self, _rl: &mut RaylibHandle) {}
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32);
+    fn background(&self) -> Color {
+        Color::from_hex("cfcefc").unwrap()
+    }
+}
+
+static SCREENS: Lazy<RwLock<HashMap<String, Arc<dyn Screen>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+// the screen that was shown last frame
+static SHOWN_SCREEN: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+// registering an id again replaces the screen
+pub fn register_screen(id: &str, screen: Arc<dyn Screen>) {
+    SCREENS.write().unwrap().insert(id.to_string(), screen);
+}
+
+pub fn get_screen(id: &str) -> Option<Arc<dyn Screen>> {
+    SCREENS.read().unwrap().get(id).cloned()
+}
+
+// makes the screen the shown one, calling on_exit and on_enter and swapping the screen's
+// listeners when it changed
+pub fn show_screen(id: &str, screen: &Arc<dyn Screen>) {
+    let previous = {
+        let mut shown = SHOWN_SCREEN.lock().unwrap();
+        if shown.as_deref() == Some(id) {
+            return;
+        }
+        shown.replace(id.to_string())
+    };
+    if let Some(previous) = previous.and_then(|previous| get_screen(&previous)) {
+        previous.on_exit();
+    }
+    let mut listeners = ListenerGroup::new();
+    screen.listeners(&mut listeners);
+    replace_group(SCREEN_LISTENERS, listeners);
+    screen.on_enter();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::events::types::event::Event;
+
+    static TEST_EVENT: Lazy<Event<()>> = Lazy::new(Event::new);
+
+    struct CountingScreen {
+        calls: Arc<Mutex<Vec<&'static str>>>,
+        listens: bool,
+    }
+
+    impl Screen for CountingScreen {
+        fn on_enter(&self) {
+            self.calls.lock().unwrap().push("enter");
+        }
+
+        fn on_exit(&self) {
+            self.calls.lock().unwrap().push("exit");
+        }
+
+        fn listeners(&self, group: &mut ListenerGroup) {
+            if self.listens {
+                let calls = self.calls.clone();
+                group.add(TEST_EVENT.on_event(move |_| calls.lock().unwrap().push("event")));
+            }
+        }
+
+        fn draw(&self, _d: &mut RaylibDrawHandle, _ui: &mut UiContext, _scale_x: f32, _scale_y: f32) {}
+    }
+
+    #[test]
+    fn it_does_enter_and_exit_registered_screens() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        register_screen("test.first", Arc::new(CountingScreen { calls: calls.clone(), listens: true }));
+        register_screen("test.second", Arc::new(CountingScreen { calls: calls.clone(), listens: false }));
+        let first = get_screen("test.first").unwrap();
+        let second = get_screen("test.second").unwrap();
+
+        show_screen("test.first", &first);
+        show_screen("test.first", &first);
+        TEST_EVENT.call(());
+        show_screen("test.second", &second);
+        TEST_EVENT.call(());
+
+        assert_eq!(*calls.lock().unwrap(), vec!["enter", "event", "exit", "enter"]);
+        assert!(get_screen("test.missing").is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use raylib::{color::Color, core::input::key_from_i32, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::{
+        navigation,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{
+        render::scaled_value,
+        screen::Screen,
+        screens::{rebind_screen::REBIND_SCREEN, settings_screen::save_settings},
+    },
+    managers::game_manager::{
+        key_name, read_game_manager, write_game_manager_rebinding, write_game_manager_save_data,
+        Control,
+    },
+};
+
+pub struct ControlsScreen;
+
+impl Screen for ControlsScreen {
+    // not when the rebind dialog opens over it, only when it is left
+    fn on_exit(&self) {
+        if read_game_manager().screen != REBIND_SCREEN {
+            save_settings();
+        }
+    }
+
+    // the rebind dialog hands back the code of the key that was pressed, or nothing if cancelled
+    fn update(&self) {
+        let Some(result) = navigation::take_result() else {
+            return;
+        };
+        let Some(control) = read_game_manager().rebinding else {
+            return;
+        };
+        write_game_manager_rebinding(None);
+        if result.from != REBIND_SCREEN {
+            return;
+        }
+        if let Some(key) = result.value.parse().ok().and_then(key_from_i32) {
+            let mut save_data = read_game_manager().save_data.clone();
+            save_data.settings.bind(control, key);
+            write_game_manager_save_data(save_data);
+        }
+    }
+
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Controls".to_string(),
+            title.height,
+        );
+
+        let settings = read_game_manager().save_data.settings.clone();
+        let mut column = Stack::vertical(window.inset(0, scaled_value(180, scale_y), 0, 0), scaled_value(12, scale_y));
+        for control in Control::ALL {
+            let row = column.next(scaled_value(500, scale_x), scaled_value(30, scale_y));
+            ui::text::text(
+                d,
+                row.x + scaled_value(100, scale_x),
+                row.center_y(),
+                Color::BLACK,
+                control.name().to_string(),
+                scaled_value(20, scale_y),
+            );
+            let key = row.anchored(Anchor::Right, scaled_value(200, scale_x), row.height, 0, 0);
+            if ui::button::button(
+                d,
+                ui,
+                key.width,
+                key.height,
+                key.center_x(),
+                key.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                key_name(settings.key_for(control)),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::Rebind(control));
+            }
+        }
+
+        column.skip(scaled_value(20, scale_y));
+        let buttons = [("Reset to defaults", Action::ResetControls), ("Back", Action::Back)];
+        for (label, action) in buttons {
+            let rect = column.next(scaled_value(200, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                label.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(action);
+            }
+        }
+    }
+}
@@ -1,50 +1,69 @@
-use raylib::{color::Color, prelude::RaylibDraw};
+use raylib::{color::Color, prelude::RaylibDrawHandle};
 
 use crate::engine::{
-    common::ui,
-    lib::RAYLIB_STATE,
-    listeners::base::render::render::{get_scaling_factors, scaled_value}, managers::{game_manager::{self, Block}, game_statics::read_game_statics},
+    common::ui::{
+        self,
+        context::UiContext,
+        layout::{stack_length, Anchor, Rect, Stack},
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen}, managers::game_manager::{self, Block},
 };
 
-pub fn render_create_shape() {
-    let mut state = RAYLIB_STATE.lock().unwrap();
-    if let Some(ref mut raylib_state) = *state {
-        let mut d = raylib_state.rl.begin_drawing(&raylib_state.thread);
-        let (scale_x, scale_y) = get_scaling_factors(&d);
+pub struct CreateShapeScreen;
+
+impl Screen for CreateShapeScreen {
+    // every visit starts from an empty 5x5 shape
+    fn on_enter(&self) {
+        let shape = vec![vec![0; 5]; 5];
+        let block: Block = Block {
+            layout: shape,
+            color: Color::AQUA,
+            can_rotate: false,
+            ..Default::default()
+        };
+        game_manager::write_game_manager_custom_block(block);
+    }
 
-        // if shape is empty fill with 0
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
         let game_manager = game_manager::read_game_manager();
-        if game_manager.custom_block.layout.len() == 0 {
-            let shape = vec![vec![0; 5]; 5];
-            let block: Block = Block {
-                layout: shape,
-                color: Color::AQUA,
-                can_rotate: false,
-                ..Default::default()
-            };
-            game_manager::write_game_manager_custom_block(block);
-        }
 
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
         ui::text::text(
-            &mut d,
-            scaled_value(800, scale_x),
-            scaled_value(116, scale_y),
+            d,
+            title.center_x(),
+            title.center_y(),
             Color::BLACK,
             "Create Shape".to_string(),
-            scaled_value(100, scale_y),
+            title.height,
+        );
+
+        let cell_width = scaled_value(30, scale_x);
+        let cell_height = scaled_value(30, scale_y);
+        let grid = window.anchored(
+            Anchor::Top,
+            stack_length(5, cell_width, scaled_value(5, scale_x)),
+            stack_length(5, cell_height, scaled_value(5, scale_y)),
+            0,
+            scaled_value(285, scale_y),
         );
 
         // create a 5x5 grid of buttons
+        let mut rows = Stack::vertical(grid, scaled_value(5, scale_y));
         for y in 0..5 {
+            let row = rows.next(grid.width, cell_height);
+            let mut cells = Stack::horizontal(row, scaled_value(5, scale_x));
             for x in 0..5 {
+                let cell = cells.next(cell_width, cell_height);
                 // create a grid of 30x30 buttons
                 let game_manager = game_manager::read_game_manager();
                 
-                if ui::check_box::check_box(&mut d, 
-                scaled_value(30, scale_x),
-                scaled_value(30, scale_y),
-                scaled_value(730 + x * 35, scale_x),    
-                scaled_value(300 + y * 35, scale_y),
+                if ui::check_box::check_box(d, ui, 
+                cell.width,
+                cell.height,
+                cell.center_x(),
+                cell.center_y(),
                 Color::WHITE,
                 Color::GRAY,
                 Color::AQUA,
@@ -63,19 +82,27 @@ pub fn render_create_shape() {
             }
         }
 
-        ui::text::text(&mut d, 
-            scaled_value(600, scale_x),
-            scaled_value(300, scale_y),
+        // the options sit in a column to the left of the grid
+        let mut options = Stack::vertical(
+            grid.anchored(Anchor::TopLeft, scaled_value(100, scale_x), grid.height, scaled_value(-165, scale_x), 0),
+            0,
+        );
+        let rotatable = options.next(scaled_value(100, scale_x), scaled_value(30, scale_y));
+        ui::text::text(d, 
+            rotatable.center_x(),
+            rotatable.center_y(),
             Color::BLACK,
             "Rotatable?".to_string(),
             scaled_value(20, scale_y),
         );
 
-        if ui::check_box::check_box(&mut d, 
-            scaled_value(30, scale_x), 
-            scaled_value(30, scale_y),
-            scaled_value(600, scale_x),    
-            scaled_value(350, scale_y),
+        options.skip(scaled_value(20, scale_y));
+        let rotate = options.next(cell_width, cell_height);
+        if ui::check_box::check_box(d, ui, 
+            rotate.width,
+            rotate.height,
+            rotate.center_x(),
+            rotate.center_y(),
             Color::WHITE,
             Color::GRAY,
             Color::AQUA,
@@ -86,19 +113,22 @@ pub fn render_create_shape() {
             game_manager::write_game_manager_custom_block(block);
         }
 
-        ui::text::text(&mut d, 
-            scaled_value(600, scale_x),
-            scaled_value(440, scale_y),
+        options.skip(scaled_value(60, scale_y));
+        let color_label = options.next(scaled_value(100, scale_x), scaled_value(30, scale_y));
+        ui::text::text(d, 
+            color_label.center_x(),
+            color_label.center_y(),
             Color::BLACK,
             "Color".to_string(),
             scaled_value(20, scale_y),
         );
 
-        let color = ui::color_picker::color_picker(&mut d, 
-            scaled_value(600, scale_x),    
-            scaled_value(500, scale_y),
-            scaled_value(100, scale_x),
-            scaled_value(100, scale_y),
+        let picker = options.next(scaled_value(100, scale_x), scaled_value(100, scale_y));
+        let color = ui::color_picker::color_picker(d, ui, 
+            picker.center_x(),
+            picker.center_y(),
+            picker.width,
+            picker.height,
             game_manager.custom_block.color,
         );
 
@@ -108,12 +138,18 @@ pub fn render_create_shape() {
         block.color = color;
         game_manager::write_game_manager_custom_block(block);
 
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(550, scale_y),
+        // save and back go under the grid
+        let mut buttons = Stack::vertical(window.inset(0, grid.bottom() + scaled_value(30, scale_y), 0, 0), scaled_value(20, scale_y));
+        let save = buttons.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+        let back = buttons.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
             Color::WHITE,
             Color::GRAY,
             "Back".to_string(),
@@ -121,16 +157,18 @@ pub fn render_create_shape() {
             Color::BLACK,
             Color::BLACK,
             false,
-            format!("{}.{}", read_game_statics().url, "button.main_menu".to_string()),
-        );
+        ) {
+            BUTTON_EVENT.post(Action::Back);
+        }
 
 
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(500, scale_y),
+        if ui::button::button(
+            d,
+            ui,
+            save.width,
+            save.height,
+            save.center_x(),
+            save.center_y(),
             Color::WHITE,
             Color::GRAY,
             "Save".to_string(),
@@ -138,13 +176,8 @@ pub fn render_create_shape() {
             Color::BLACK,
             Color::BLACK,
             false,
-            format!("{}.{}", read_game_statics().url, "button.save_shape".to_string()),
-        );
-
-
-
-
-
-        d.clear_background(Color::from_hex("cfcefc".as_ref()).unwrap());
+        ) {
+            BUTTON_EVENT.post(Action::SaveShape);
+        }
     }
 }
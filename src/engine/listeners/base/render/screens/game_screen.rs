@@ -1,95 +1,93 @@
-use raylib::{color::Color, math::rrect, prelude::RaylibDraw};
+use raylib::{color::Color, math::rrect, prelude::{RaylibDraw, RaylibDrawHandle}};
 
 use crate::engine::{
-    common::ui,
-    lib::RAYLIB_STATE,
-    listeners::base::render::{
-        components::{game_over_component::render_game_over, pause_component::render_pause_menu},
-        render::{get_scaling_factors, scaled_value},
+    audio,
+    common::ui::{
+        self,
+        context::UiContext,
+        layout::{Rect, Stack},
+    },
+    events::events::GAMEPLAY_EVENT,
+    listeners::{
+        base::render::{
+            components::{game_over_component::render_game_over, pause_component::render_pause_menu},
+            render::{format_ticks, scaled_value},
+            screen::Screen,
+        },
+        lib::ListenerGroup,
+    },
+    managers::{
+        game_manager::{key_name, read_game_manager, Control, GameMode},
+        game_state::{read_game_state, GARBAGE_CELL, GHOST_CELL},
+        game_statics::read_game_statics,
     },
-    managers::{game_manager::read_game_manager, game_state::read_game_state},
 };
 
-pub fn render_game() {
-    let mut state = RAYLIB_STATE.lock().unwrap();
-    if let Some(ref mut raylib_state) = *state {
-        let mut d = raylib_state.rl.begin_drawing(&raylib_state.thread);
-        d.clear_background(Color::from_hex("cfcefc".as_ref()).unwrap());
+pub struct GameScreen;
 
-        let (scale_x, scale_y) = get_scaling_factors(&d);
+impl Screen for GameScreen {
+    // sound effects follow the game on screen, so nothing plays once it is left
+    fn listeners(&self, group: &mut ListenerGroup) {
+        group.add(GAMEPLAY_EVENT.on_event(audio::lib::on_gameplay));
+    }
 
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
         d.draw_fps(scaled_value(10, scale_x), scaled_value(10, scale_y));
 
-        // Draw other UI elements like score, level, and speed using scaled positions
-        ui::text::text(
-            &mut d,
-            // to the left of held piece
-            scaled_value(300, scale_x),
-            scaled_value(220, scale_y),
-            Color::BLACK,
-            "Score".to_string(),
-            scaled_value(20, scale_y),
-        );
-
-        ui::text::text(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(240, scale_y),
-            Color::BLACK,
-            read_game_state().game_data.score.to_string(),
-            scaled_value(20, scale_y),
-        );
-        // high score bellow
-        ui::text::text(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(270, scale_y),
-            Color::BLACK,
-            "High Score".to_string(),
-            scaled_value(20, scale_y),
-        );
-        ui::text::text(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(290, scale_y),
-            Color::BLACK,
-            if read_game_manager().save_data.best_game.score > read_game_state().game_data.score {
-                read_game_manager().save_data.best_game.score.to_string()
-            } else {
-                read_game_state().game_data.score.to_string()
-            },
-            scaled_value(20, scale_y),
-        );
-
-        // level bellow
-        ui::text::text(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(320, scale_y),
-            Color::BLACK,
-            "Level".to_string(),
-            scaled_value(20, scale_y),
-        );
+        // the stats to the left of the held piece, the ones every mode has then the mode's own
+        let game_data = read_game_state().game_data.clone();
+        let high_score = read_game_manager().save_data.best_game.score.max(game_data.score);
+        let mut stats = vec![
+            ("Score", game_data.score.to_string()),
+            ("High Score", high_score.to_string()),
+            ("Level", game_data.level.to_string()),
+        ];
+        match game_data.mode {
+            // zen has no top-out, so show how far the session has gone instead
+            GameMode::Zen => stats.extend([
+                ("Lines", game_data.lines_cleared.to_string()),
+                ("Pieces", game_data.pieces_placed.to_string()),
+            ]),
+            GameMode::Dig => stats.extend([
+                ("Garbage Left", read_game_state().garbage_lines_remaining.max(0).to_string()),
+                ("Time", format_ticks(game_data.ticks_played)),
+            ]),
+            GameMode::Survival => stats.extend([
+                ("Lines", game_data.lines_cleared.to_string()),
+                ("Survived", format_ticks(game_data.ticks_played)),
+            ]),
+            _ => {}
+        }
 
-        ui::text::text(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(340, scale_y),
-            Color::BLACK,
-            read_game_state().game_data.level.to_string(),
-            scaled_value(20, scale_y),
-        );
+        let line_height = scaled_value(20, scale_y);
+        let column = Rect::new(scaled_value(300, scale_x), scaled_value(210, scale_y), 0, 0);
+        let mut column = Stack::vertical(column, 0);
+        for (label, value) in stats {
+            for line in [label.to_string(), value] {
+                let row = column.next(0, line_height);
+                ui::text::text(d, row.center_x(), row.center_y(), Color::BLACK, line, line_height);
+            }
+            column.skip(scaled_value(10, scale_y));
+        }
 
         let board_x = scaled_value(624, scale_x); // Top-left X position of the game board
         let board_y = scaled_value(56, scale_y); // Top-left Y position of the game board
         let cell_size = scaled_value(16, scale_x); // Size of each cell, scaled based on screen size
+        let board = Rect::new(
+            board_x,
+            board_y,
+            read_game_state().arena[0].len() as i32 * cell_size,
+            read_game_state().arena.len() as i32 * cell_size,
+        );
 
         for (y, row) in read_game_state().arena.iter().enumerate() {
             for (x, &val) in row.iter().enumerate() {
                 let cell_x = board_x + (x as i32 * cell_size); // Calculate the cell's X position
                 let cell_y = board_y + (y as i32 * cell_size); // Calculate the cell's Y position
 
-                if val != 0 && val != 2 {
+                if val == GARBAGE_CELL {
+                    d.draw_rectangle(cell_x, cell_y, cell_size, cell_size, Color::DARKGRAY);
+                } else if val != 0 && val != GHOST_CELL {
                     d.draw_rectangle(
                         cell_x,
                         cell_y,
@@ -106,7 +104,7 @@ pub fn render_game() {
                     );
                 }
 
-                if val == 2 {
+                if val == GHOST_CELL {
                     d.draw_rectangle(cell_x, cell_y, cell_size, cell_size, Color::GRAY);
                 }
 
@@ -125,13 +123,66 @@ pub fn render_game() {
                 }
             }
         }
+
+        // a puzzle's own lines carry on down the stats column
+        if read_game_state().game_data.mode == GameMode::Puzzle {
+            let mut lines = vec![];
+            if let Some(puzzle) = read_game_manager().puzzle.clone() {
+                lines.push((puzzle.name.clone(), Color::BLACK, 0));
+                lines.push((puzzle.objective.describe(), Color::BLACK, 10));
+            }
+            lines.push(("Pieces Left".to_string(), Color::BLACK, 0));
+            lines.push((read_game_state().piece_queue.len().to_string(), Color::BLACK, 20));
+            lines.push((
+                format!(
+                    "{} to retry",
+                    key_name(read_game_manager().save_data.settings.key_for(Control::Retry))
+                ),
+                Color::DARKGRAY,
+                0,
+            ));
+
+            for (line, color, gap) in lines {
+                let row = column.next(0, line_height);
+                ui::text::text(d, row.center_x(), row.center_y(), color, line, line_height);
+                column.skip(scaled_value(gap, scale_y));
+            }
+        }
+
+        // warn under the board that the next garbage row is about to come up
+        if read_game_state().game_data.mode == GameMode::Survival
+            && !read_game_state().game_over
+            && read_game_state().rise_ticks <= read_game_statics().survival_warning_ticks
+        {
+            let progress = 1.0
+                - read_game_state().rise_ticks as f32
+                    / read_game_statics().survival_warning_ticks as f32;
+
+            d.draw_rectangle(
+                board.x,
+                board.bottom() + scaled_value(4, scale_y),
+                (board.width as f32 * progress) as i32,
+                scaled_value(8, scale_y),
+                Color::RED,
+            );
+
+            ui::text::text(
+                d,
+                board.center_x(),
+                board.bottom() + scaled_value(28, scale_y),
+                Color::RED,
+                "Garbage Incoming".to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+
         let held_x = scaled_value(500, scale_x);
         let held_y = scaled_value(220, scale_y);
 
         let held_size = scaled_value(16, scale_x);
 
         ui::text::text(
-            &mut d,
+            d,
             held_x,
             held_y,
             Color::BLACK,
@@ -162,7 +213,7 @@ pub fn render_game() {
         let queue_size = scaled_value(8, scale_y);
 
         ui::text::text(
-            &mut d,
+            d,
             queue_x,
             queue_y,
             Color::BLACK,
@@ -190,12 +241,17 @@ pub fn render_game() {
             }
         }
 
+        // the panels are modal, so nothing drawn under them takes input while they are open
         if read_game_state().game_over {
-            render_game_over(&mut d, scale_x, scale_y);
+            ui.begin_modal();
+            render_game_over(d, ui, board, scale_x, scale_y);
+            ui.end_modal();
         }
 
         if !read_game_manager().running {
-            render_pause_menu(&mut d, scale_x, scale_y);
+            ui.begin_modal();
+            render_pause_menu(d, ui, board, scale_x, scale_y);
+            ui.end_modal();
         }
     }
 }
@@ -0,0 +1,163 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::{
+        leaderboard::{self, Ranking},
+        ui::{
+            self,
+            context::UiContext,
+            layout::{stack_length, Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::{format_ticks, scaled_value}, screen::Screen},
+    managers::game_manager::{read_game_manager, GameMode},
+};
+
+const MODES: [GameMode; 4] = [GameMode::Marathon, GameMode::Zen, GameMode::Dig, GameMode::Survival];
+// rank, name, the ranked value, lines and date
+const COLUMN_WIDTHS: [i32; 5] = [120, 200, 160, 120, 160];
+const ROW_HEIGHT: i32 = 30;
+
+pub struct LeaderboardScreen;
+
+impl Screen for LeaderboardScreen {
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let game_manager = read_game_manager();
+        let mode = game_manager.leaderboard_mode;
+
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(60, scale_y), 0, scaled_value(70, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Leaderboards".to_string(),
+            title.height,
+        );
+
+        let tab_width = scaled_value(150, scale_x);
+        let tab_spacing = scaled_value(50, scale_x);
+        let tab_row = window.anchored(
+            Anchor::Top,
+            stack_length(MODES.len(), tab_width, tab_spacing),
+            scaled_value(30, scale_y),
+            0,
+            scaled_value(185, scale_y),
+        );
+        let mut tabs = Stack::horizontal(tab_row, tab_spacing);
+        for tab in MODES.iter() {
+            let rect = tabs.next(tab_width, tab_row.height);
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                if *tab == mode { Color::SKYBLUE } else { Color::WHITE },
+                Color::GRAY,
+                tab.name().to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::LeaderboardMode(*tab));
+            }
+        }
+
+        let column_spacing = scaled_value(20, scale_x);
+        let table_width = COLUMN_WIDTHS.iter().map(|width| scaled_value(*width, scale_x)).sum::<i32>()
+            + column_spacing * (COLUMN_WIDTHS.len() as i32 - 1);
+        let mut rows = Stack::vertical(window.inset(0, scaled_value(243, scale_y), 0, 0), scaled_value(5, scale_y));
+
+        // the headers set where each column is
+        let header = rows.next(table_width, scaled_value(ROW_HEIGHT, scale_y));
+        let mut header_row = Stack::horizontal(header, column_spacing);
+        let columns: Vec<Rect> = COLUMN_WIDTHS
+            .iter()
+            .map(|width| header_row.next(scaled_value(*width, scale_x), header.height))
+            .collect();
+
+        let value_name = match Ranking::of(mode) {
+            Some(Ranking::HighestScore) | None => "Score",
+            Some(Ranking::FastestTime) => "Clear Time",
+            Some(Ranking::LongestTime) => "Survived",
+        };
+        for (column, name) in columns.iter().zip(["Rank", "Name", value_name, "Lines", "Date"]) {
+            ui::text::text(
+                d,
+                column.center_x(),
+                column.center_y(),
+                Color::DARKGRAY,
+                name.to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+
+        let board = leaderboard::board(&game_manager.save_data.leaderboards, mode);
+        if board.is_empty() {
+            let rect = rows.next(window.width, scaled_value(ROW_HEIGHT, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::BLACK,
+                "No records yet".to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+
+        for (rank, entry) in board.iter().enumerate() {
+            let row = rows.next(table_width, scaled_value(ROW_HEIGHT, scale_y));
+            let value = match Ranking::of(mode) {
+                Some(Ranking::HighestScore) | None => entry.score.to_string(),
+                Some(_) => format_ticks(entry.ticks),
+            };
+            let cells = [
+                format!("#{}", rank + 1),
+                entry.name.clone(),
+                value,
+                entry.lines_cleared.to_string(),
+                entry.date.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string(),
+            ];
+            for (column, cell) in columns.iter().zip(cells) {
+                ui::text::text(
+                    d,
+                    column.center_x(),
+                    row.center_y(),
+                    Color::BLACK,
+                    cell,
+                    scaled_value(20, scale_y),
+                );
+            }
+        }
+
+        let back = window.anchored(
+            Anchor::Bottom,
+            scaled_value(115, scale_x),
+            scaled_value(30, scale_y),
+            0,
+            scaled_value(-65, scale_y),
+        );
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Back".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            false,
+        ) {
+            BUTTON_EVENT.post(Action::Back);
+        }
+    }
+}
@@ -1,90 +1,111 @@
-use raylib::{color::Color, prelude::RaylibDraw};
+use raylib::{color::Color, prelude::RaylibDrawHandle};
 
 use crate::engine::{
-    common::ui,
-    lib::RAYLIB_STATE,
-    listeners::base::render::render::{get_scaling_factors, scaled_value}, managers::game_statics::read_game_statics,
+    common::{
+        storage::profile,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::game_manager::read_game_manager,
 };
 
-pub fn render_main_menu() {
-    let mut state = RAYLIB_STATE.lock().unwrap();
-    if let Some(ref mut raylib_state) = *state {
-        let mut d = raylib_state.rl.begin_drawing(&raylib_state.thread);
-        let (scale_x, scale_y) = get_scaling_factors(&d);
+pub struct MainScreen;
 
+impl Screen for MainScreen {
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
         ui::text::text(
-            &mut d,
-            scaled_value(800, scale_x),
-            scaled_value(116, scale_y),
+            d,
+            title.center_x(),
+            title.center_y(),
             Color::BLACK,
             "Revris".to_string(),
-            scaled_value(100, scale_y),
+            title.height,
         );
 
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(300, scale_y),
-            Color::WHITE,
-            Color::GRAY,
-            "Play".to_string(),
-            scaled_value(20, scale_y),
-            Color::BLACK,
-            Color::BLACK,
-            false,
-            format!("{}.{}", read_game_statics().url, "button.play".to_string()),
-        );
+        if let Some(id) = profile::active() {
+            let game_manager = read_game_manager();
+            let name = game_manager.profiles.index.find(&id).map_or(id.clone(), |p| p.name.clone());
+            ui::text::text(
+                d,
+                title.center_x(),
+                title.bottom() + scaled_value(34, scale_y),
+                Color::DARKGRAY,
+                format!("Playing as {}", name),
+                scaled_value(20, scale_y),
+            );
+        }
 
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(350, scale_y),
-            Color::WHITE,
-            Color::GRAY,
-            "Blocks".to_string(),
-            scaled_value(20, scale_y),
-            Color::BLACK,
-            Color::BLACK,
-            false,
-            format!("{}.{}", read_game_statics().url, "button.blocks".to_string()),
-        );
+        let buttons = [
+            ("Play", Action::Play),
+            ("Blocks", Action::Blocks),
+            ("Stats", Action::Stats),
+            ("Leaderboards", Action::Leaderboards),
+            ("Settings", Action::Settings),
+            ("Profiles", Action::Profiles),
+            ("Quit", Action::Quit),
+        ];
+        let mut column = Stack::vertical(window.inset(0, scaled_value(285, scale_y), 0, 0), scaled_value(20, scale_y));
+        for (label, action) in buttons {
+            let rect = column.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                label.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(action);
+            }
+        }
 
-        // disabled settings button
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(400, scale_y),
-            Color::WHITE,
-            Color::GRAY,
-            "Settings".to_string(),
-            scaled_value(20, scale_y),
-            Color::BLACK,
-            Color::BLACK,
-            true,
-            "".to_string(),
-        );
+        if let Some(notice) = read_game_manager().notice.clone() {
+            column.skip(scaled_value(25, scale_y));
+            // one sentence per line so long notices fit on screen
+            for line in notice.split_inclusive(". ") {
+                let rect = column.next(window.width, scaled_value(20, scale_y));
+                ui::text::text(
+                    d,
+                    rect.center_x(),
+                    rect.center_y(),
+                    Color::DARKRED,
+                    line.trim().to_string(),
+                    rect.height,
+                );
+            }
 
-        ui::button::button(
-            &mut d,
-            scaled_value(115, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(450, scale_y),
-            Color::WHITE,
-            Color::GRAY,
-            "Quit".to_string(),
-            scaled_value(20, scale_y),
-            Color::BLACK,
-            Color::BLACK,
-            false,
-            format!("{}.{}", read_game_statics().url, "button.quit".to_string()),
-        );
-        d.clear_background(Color::from_hex("cfcefc".as_ref()).unwrap());
+            let rect = column.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                "OK".to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::DismissNotice);
+            }
+        }
     }
 }
@@ -1,4 +1,12 @@
+pub mod controls_screen;
+pub mod create_shape_screen;
 pub mod game_screen;
+pub mod leaderboard_screen;
 pub mod main_screen;
+pub mod mode_select_screen;
+pub mod profiles_screen;
+pub mod puzzle_select_screen;
+pub mod rebind_screen;
+pub mod settings_screen;
+pub mod stats_screen;
 pub mod unknown_screen;
-pub mod create_shape_screen;
\ No newline at end of file
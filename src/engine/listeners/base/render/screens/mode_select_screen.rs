@@ -0,0 +1,59 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::ui::{
+        self,
+        context::UiContext,
+        layout::{Anchor, Rect, Stack},
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::game_manager::GameMode,
+};
+
+pub struct ModeSelectScreen;
+
+impl Screen for ModeSelectScreen {
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Select Mode".to_string(),
+            title.height,
+        );
+
+        let buttons = [
+            ("Marathon", Action::StartGame(GameMode::Marathon)),
+            ("Zen", Action::StartGame(GameMode::Zen)),
+            ("Dig", Action::StartGame(GameMode::Dig)),
+            ("Survival", Action::StartGame(GameMode::Survival)),
+            ("Puzzles", Action::Puzzles),
+            ("Back", Action::Back),
+        ];
+        let mut column = Stack::vertical(window.inset(0, scaled_value(285, scale_y), 0, 0), scaled_value(20, scale_y));
+        for (label, action) in buttons {
+            let rect = column.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                label.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(action);
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use raylib::{color::Color, ffi::KeyboardKey, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::{
+        storage::profile,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::{
+        game_manager::{read_game_manager, write_game_manager_profiles},
+        game_statics::read_game_statics,
+    },
+};
+
+// the name, rename and delete columns of each row
+const NAME_WIDTH: i32 = 250;
+const ACTION_WIDTH: i32 = 100;
+const ROW_WIDTH: i32 = NAME_WIDTH + 2 * ACTION_WIDTH + 2 * 20;
+const ROW_HEIGHT: i32 = 35;
+
+pub struct ProfilesScreen;
+
+impl Screen for ProfilesScreen {
+    fn on_enter(&self) {
+        let mut profiles = read_game_manager().profiles.clone();
+        profiles.editing = None;
+        profiles.pending_delete = None;
+        write_game_manager_profiles(profiles);
+    }
+
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let mut profiles = read_game_manager().profiles.clone();
+        let active = profile::active();
+
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Profiles".to_string(),
+            title.height,
+        );
+
+        let mut rows = Stack::vertical(window.inset(0, scaled_value(232, scale_y), 0, 0), scaled_value(10, scale_y));
+        if profiles.index.profiles.is_empty() {
+            let rect = rows.next(window.width, scaled_value(ROW_HEIGHT, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::BLACK,
+                "Create a profile to start playing".to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+
+        let editing = profiles.editing.is_some();
+        for entry in profiles.index.profiles.iter() {
+            let row = rows.next(scaled_value(ROW_WIDTH, scale_x), scaled_value(ROW_HEIGHT, scale_y));
+            let mut cells = Stack::horizontal(row, scaled_value(20, scale_x));
+            let name = cells.next(scaled_value(NAME_WIDTH, scale_x), row.height);
+            let rename = cells.next(scaled_value(ACTION_WIDTH, scale_x), row.height);
+            let delete = cells.next(scaled_value(ACTION_WIDTH, scale_x), row.height);
+
+            let is_active = active.as_ref() == Some(&entry.id);
+            if ui::button::button(
+                d,
+                ui,
+                name.width,
+                name.height,
+                name.center_x(),
+                name.center_y(),
+                if is_active { Color::SKYBLUE } else { Color::WHITE },
+                Color::GRAY,
+                entry.name.clone(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                editing,
+            ) {
+                BUTTON_EVENT.post(Action::SelectProfile(entry.id.clone()));
+            }
+
+            if ui::button::button(
+                d,
+                ui,
+                rename.width,
+                rename.height,
+                rename.center_x(),
+                rename.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                "Rename".to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                editing,
+            ) {
+                BUTTON_EVENT.post(Action::RenameProfile(entry.id.clone()));
+            }
+
+            let confirming = profiles.pending_delete.as_ref() == Some(&entry.id);
+            if ui::button::button(
+                d,
+                ui,
+                delete.width,
+                delete.height,
+                delete.center_x(),
+                delete.center_y(),
+                if confirming { Color::PINK } else { Color::WHITE },
+                Color::GRAY,
+                if confirming { "Sure?" } else { "Delete" }.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                editing || is_active,
+            ) {
+                BUTTON_EVENT.post(Action::DeleteProfile(entry.id.clone()));
+            }
+        }
+
+        // the row for a new or renamed profile lines up with the ones above it
+        rows.skip(scaled_value(20, scale_y));
+        let row = rows.next(scaled_value(ROW_WIDTH, scale_x), scaled_value(ROW_HEIGHT, scale_y));
+        let mut cells = Stack::horizontal(row, scaled_value(20, scale_x));
+        let field = cells.next(scaled_value(NAME_WIDTH, scale_x), row.height);
+        if let Some(mut edit) = profiles.editing.clone() {
+            let save = cells.next(scaled_value(ACTION_WIDTH, scale_x), row.height);
+            let cancel = cells.next(scaled_value(ACTION_WIDTH, scale_x), row.height);
+
+            let name = ui::text_field::text_field(
+                d,
+                ui,
+                field.width,
+                field.height,
+                field.center_x(),
+                field.center_y(),
+                &edit.name,
+                scaled_value(20, scale_y),
+                read_game_statics().player_name_length,
+            );
+            if name != edit.name {
+                edit.name = name;
+                profiles.editing = Some(edit.clone());
+                write_game_manager_profiles(profiles.clone());
+            }
+
+            let trimmed = edit.name.trim();
+            let valid = !trimmed.is_empty()
+                && !profiles.index.name_taken(trimmed, edit.id.as_deref());
+            if ui::button::button(
+                d,
+                ui,
+                save.width,
+                save.height,
+                save.center_x(),
+                save.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                if edit.id.is_some() { "Rename" } else { "Create" }.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                !valid,
+            ) {
+                BUTTON_EVENT.post(Action::SaveProfile);
+            }
+
+            if ui::button::button(
+                d,
+                ui,
+                cancel.width,
+                cancel.height,
+                cancel.center_x(),
+                cancel.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                "Cancel".to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::CancelProfileEdit);
+            }
+
+            if !trimmed.is_empty() && !valid {
+                ui::text::text(
+                    d,
+                    field.center_x(),
+                    field.bottom() + scaled_value(17, scale_y),
+                    Color::DARKRED,
+                    "That name is already taken".to_string(),
+                    scaled_value(16, scale_y),
+                );
+            }
+            if valid && d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                BUTTON_EVENT.post(Action::SaveProfile);
+            }
+        } else {
+            if ui::button::button(
+                d,
+                ui,
+                field.width,
+                field.height,
+                field.center_x(),
+                field.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                "New Profile".to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::NewProfile);
+            }
+        }
+
+        if let Some(notice) = read_game_manager().notice.clone() {
+            let rect = window.anchored(Anchor::Bottom, window.width, scaled_value(20, scale_y), 0, scaled_value(-130, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::DARKRED,
+                notice,
+                rect.height,
+            );
+        }
+
+        // only once a profile is open is there a menu to go back to
+        let back = window.anchored(
+            Anchor::Bottom,
+            scaled_value(115, scale_x),
+            scaled_value(30, scale_y),
+            0,
+            scaled_value(-65, scale_y),
+        );
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Back".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            active.is_none(),
+        ) {
+            BUTTON_EVENT.post(Action::Back);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    common::{
+        puzzle::PUZZLE_DIR,
+        storage,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::game_manager::{key_name, read_game_manager, Control},
+};
+
+pub struct PuzzleSelectScreen;
+
+impl Screen for PuzzleSelectScreen {
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Puzzles".to_string(),
+            title.height,
+        );
+
+        let game_manager = read_game_manager();
+        if game_manager.puzzles.is_empty() {
+            ui::text::text(
+                d,
+                window.center_x(),
+                scaled_value(300, scale_y),
+                Color::BLACK,
+                match storage::lib::full_path(PUZZLE_DIR) {
+                    Ok(path) => format!("No puzzles found in {}", path),
+                    Err(e) => format!("No puzzles found because {}", e),
+                },
+                scaled_value(20, scale_y),
+            );
+        }
+
+        let mut list = Stack::vertical(window.inset(0, scaled_value(235, scale_y), 0, 0), scaled_value(10, scale_y));
+        for puzzle in game_manager.puzzles.iter() {
+            let solved = game_manager.save_data.solved_puzzles.contains(&puzzle.id);
+            let rect = list.next(scaled_value(400, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                if solved { Color::LIGHTGRAY } else { Color::WHITE },
+                Color::GRAY,
+                format!(
+                    "{} - {}{}",
+                    puzzle.name,
+                    puzzle.objective.describe(),
+                    if solved { " (solved)" } else { "" }
+                ),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::StartPuzzle(puzzle.id.clone()));
+            }
+        }
+
+        if let Some(notice) = &game_manager.notice {
+            let rect = window.anchored(Anchor::Bottom, window.width, scaled_value(20, scale_y), 0, scaled_value(-130, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::DARKRED,
+                notice.clone(),
+                rect.height,
+            );
+        }
+
+        let hint = window.anchored(Anchor::Bottom, window.width, scaled_value(20, scale_y), 0, scaled_value(-90, scale_y));
+        ui::text::text(
+            d,
+            hint.center_x(),
+            hint.center_y(),
+            Color::BLACK,
+            format!(
+                "Press {} during a puzzle to retry it",
+                key_name(game_manager.save_data.settings.key_for(Control::Retry))
+            ),
+            hint.height,
+        );
+
+        let back = window.anchored(
+            Anchor::Bottom,
+            scaled_value(115, scale_x),
+            scaled_value(30, scale_y),
+            0,
+            scaled_value(-35, scale_y),
+        );
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Back".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            false,
+        ) {
+            BUTTON_EVENT.post(Action::Back);
+        }
+    }
+}
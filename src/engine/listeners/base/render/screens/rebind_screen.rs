@@ -0,0 +1,49 @@
+use raylib::{color::Color, ffi::KeyboardKey, prelude::RaylibDrawHandle, RaylibHandle};
+
+use crate::engine::{
+    common::{
+        navigation,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect},
+        },
+    },
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::game_manager::read_game_manager,
+};
+
+pub const REBIND_SCREEN: &str = "rebind";
+
+// waits for a key and returns its code to the screen below, escape goes back without one
+pub struct RebindScreen;
+
+impl Screen for RebindScreen {
+    fn handle_input(&self, rl: &mut RaylibHandle) {
+        if let Some(key) = rl.get_key_pressed().filter(|key| *key != KeyboardKey::KEY_ESCAPE) {
+            navigation::pop_with((key as i32).to_string());
+        }
+    }
+
+    fn draw(&self, d: &mut RaylibDrawHandle, _ui: &mut UiContext, _scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let control = read_game_manager().rebinding.map_or("", |control| control.name());
+        let prompt = window.anchored(Anchor::Center, window.width, scaled_value(40, scale_y), 0, 0);
+        ui::text::text(
+            d,
+            prompt.center_x(),
+            prompt.center_y(),
+            Color::BLACK,
+            format!("Press a key for {}", control),
+            prompt.height,
+        );
+        ui::text::text(
+            d,
+            prompt.center_x(),
+            prompt.bottom() + scaled_value(40, scale_y),
+            Color::DARKGRAY,
+            "Escape to cancel".to_string(),
+            scaled_value(20, scale_y),
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle};
+
+use crate::engine::{
+    audio::music::{self, MUSIC_DIR},
+    common::{
+        storage::{self, save_file},
+        ui::{
+            self,
+            context::UiContext,
+            layout::{stack_length, Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::game_manager::{
+        read_game_manager, write_game_manager_backups, write_game_manager_notice,
+        write_game_manager_save_data, MusicIntensity,
+    },
+};
+
+pub struct SettingsScreen;
+
+// settings change straight away and are saved when leaving the screens that change them
+pub fn save_settings() {
+    if read_game_manager().save_locked {
+        return;
+    }
+    if let Err(e) = save_file::save(&read_game_manager().save_data) {
+        write_game_manager_notice(Some(format!("Your settings could not be saved because {}.", e)));
+    }
+}
+
+impl Screen for SettingsScreen {
+    // picks up tracks added to the music folder since the game started
+    fn on_enter(&self) {
+        music::load_playlist();
+        write_game_manager_backups(save_file::list_backups().unwrap_or_default());
+    }
+
+    fn on_exit(&self) {
+        save_settings();
+    }
+
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let mut save_data = read_game_manager().save_data.clone();
+
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Settings".to_string(),
+            title.height,
+        );
+
+        let mut column = Stack::vertical(window.inset(0, scaled_value(245, scale_y), 0, 0), scaled_value(20, scale_y));
+
+        // sliders change the settings straight away, they are saved when leaving the screen
+        let volumes = [
+            ("Music", save_data.settings.music_volume),
+            ("Effects", save_data.settings.effects_volume),
+        ];
+        let mut changed = [0.0; 2];
+        for (i, (name, volume)) in volumes.iter().enumerate() {
+            let row = column.next(scaled_value(600, scale_x), scaled_value(30, scale_y));
+            ui::text::text(
+                d,
+                row.x + scaled_value(60, scale_x),
+                row.center_y(),
+                Color::BLACK,
+                name.to_string(),
+                scaled_value(20, scale_y),
+            );
+            changed[i] = ui::slider::slider(
+                d,
+                ui,
+                scaled_value(400, scale_x),
+                scaled_value(24, scale_y),
+                row.center_x(),
+                row.center_y(),
+                Color::WHITE,
+                Color::SKYBLUE,
+                *volume,
+            );
+            let percent = row.anchored(Anchor::Right, scaled_value(80, scale_x), row.height, 0, 0);
+            ui::text::text(
+                d,
+                percent.center_x(),
+                percent.center_y(),
+                Color::BLACK,
+                format!("{}%", (changed[i] * 100.0).round()),
+                scaled_value(20, scale_y),
+            );
+        }
+        if changed != [volumes[0].1, volumes[1].1] {
+            save_data.settings.music_volume = changed[0];
+            save_data.settings.effects_volume = changed[1];
+            write_game_manager_save_data(save_data.clone());
+        }
+
+        column.skip(scaled_value(40, scale_y));
+        let label = column.next(window.width, scaled_value(20, scale_y));
+        ui::text::text(
+            d,
+            label.center_x(),
+            label.center_y(),
+            Color::BLACK,
+            "Music as the level goes up".to_string(),
+            label.height,
+        );
+        let width = scaled_value(180, scale_x);
+        let spacing = scaled_value(20, scale_x);
+        let area = column.next(
+            stack_length(MusicIntensity::ALL.len(), width, spacing),
+            scaled_value(30, scale_y),
+        );
+        let mut row = Stack::horizontal(area, spacing);
+        for intensity in MusicIntensity::ALL.iter() {
+            let rect = row.next(width, area.height);
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                if *intensity == save_data.settings.music_intensity {
+                    Color::SKYBLUE
+                } else {
+                    Color::WHITE
+                },
+                Color::GRAY,
+                intensity.name().to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::MusicIntensity(*intensity));
+            }
+        }
+
+        let music_dir = storage::lib::full_path(MUSIC_DIR).unwrap_or(MUSIC_DIR.to_string());
+        let label = column.next(window.width, scaled_value(20, scale_y));
+        ui::text::text(
+            d,
+            label.center_x(),
+            label.center_y(),
+            Color::DARKGRAY,
+            format!("{} tracks found in {}", music::track_count(), music_dir),
+            label.height,
+        );
+
+        column.skip(scaled_value(40, scale_y));
+        let label = column.next(window.width, scaled_value(20, scale_y));
+        ui::text::text(
+            d,
+            label.center_x(),
+            label.center_y(),
+            Color::BLACK,
+            "Restore an earlier save".to_string(),
+            label.height,
+        );
+        let backups = read_game_manager().backups.clone();
+        let width = scaled_value(200, scale_x);
+        let area = column.next(stack_length(backups.len(), width, spacing), scaled_value(30, scale_y));
+        if backups.is_empty() {
+            ui::text::text(
+                d,
+                area.center_x(),
+                area.center_y(),
+                Color::DARKGRAY,
+                "No backups yet".to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+        // restoring writes the save, so not while it is locked
+        let locked = read_game_manager().save_locked;
+        let mut row = Stack::horizontal(area, spacing);
+        for backup in backups {
+            let rect = row.next(width, area.height);
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                save_file::backup_label(&backup),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                locked,
+            ) {
+                BUTTON_EVENT.post(Action::RestoreBackup(backup));
+            }
+        }
+
+        column.skip(scaled_value(40, scale_y));
+        let buttons = [("Controls", Action::Controls), ("Back", Action::Back)];
+        for (label, action) in buttons {
+            let rect = column.next(scaled_value(115, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                label.to_string(),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(action);
+            }
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use raylib::{color::Color, prelude::RaylibDrawHandle, RaylibHandle};
+
+use crate::engine::{
+    common::{
+        export::ExportFormat,
+        storage::history::HistoryColumn,
+        ui::{
+            self,
+            context::UiContext,
+            layout::{Anchor, Rect, Stack},
+        },
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
+    managers::{
+        game_manager::{read_game_manager, write_game_manager_stats, GameData},
+        game_statics::read_game_statics,
+    },
+};
+
+// how many games fit in the table at once
+pub const STATS_ROWS: usize = 16;
+
+// in the order of HistoryColumn::ALL, followed by the delete buttons
+const COLUMN_WIDTHS: [i32; 6] = [180, 110, 110, 110, 110, 110];
+const DELETE_WIDTH: i32 = 90;
+const ROW_HEIGHT: i32 = 30;
+const EXPORT_DIR_LENGTH: usize = 260;
+
+fn cell(game: &GameData, column: HistoryColumn) -> String {
+    match column {
+        HistoryColumn::Date => game
+            .start_time
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        HistoryColumn::Mode => game.mode.name().to_string(),
+        HistoryColumn::Score => game.score.to_string(),
+        HistoryColumn::Level => game.level.to_string(),
+        HistoryColumn::Lines => game.lines_cleared.to_string(),
+        HistoryColumn::Duration => {
+            let seconds = (game.end_time - game.start_time).num_seconds().max(0);
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        }
+    }
+}
+
+pub struct StatsScreen;
+
+impl Screen for StatsScreen {
+    fn handle_input(&self, rl: &mut RaylibHandle) {
+        // scroll a row at a time with the mouse wheel
+        let wheel = rl.get_mouse_wheel_move();
+        if wheel != 0.0 {
+            let mut stats = read_game_manager().stats.clone();
+            let last_page = stats.games.len().saturating_sub(STATS_ROWS);
+            stats.scroll = if wheel > 0.0 {
+                stats.scroll.saturating_sub(1)
+            } else {
+                (stats.scroll + 1).min(last_page)
+            };
+            write_game_manager_stats(stats);
+        }
+    }
+
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let game_manager = read_game_manager();
+        let stats = &game_manager.stats;
+
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(60, scale_y), 0, scaled_value(50, scale_y));
+        ui::text::text(
+            d,
+            title.center_x(),
+            title.center_y(),
+            Color::BLACK,
+            "Stats".to_string(),
+            title.height,
+        );
+
+        // the games on the left, the lifetime summary on the right
+        let table = window.inset(
+            scaled_value(130, scale_x),
+            scaled_value(155, scale_y),
+            scaled_value(490, scale_x),
+            scaled_value(185, scale_y),
+        );
+        let side = window.inset(
+            scaled_value(1200, scale_x),
+            scaled_value(155, scale_y),
+            scaled_value(100, scale_x),
+            scaled_value(185, scale_y),
+        );
+
+        // the header buttons set where each column is
+        let header = table.anchored(Anchor::TopLeft, table.width, scaled_value(ROW_HEIGHT, scale_y), 0, 0);
+        let mut header_row = Stack::horizontal(header, scaled_value(25, scale_x));
+        let columns: Vec<Rect> = COLUMN_WIDTHS
+            .iter()
+            .map(|width| header_row.next(scaled_value(*width, scale_x), header.height))
+            .collect();
+        let delete_column = header_row.next(scaled_value(DELETE_WIDTH, scale_x), header.height);
+
+        for (column, rect) in HistoryColumn::ALL.iter().zip(columns.iter()) {
+            let label = if stats.column == *column {
+                format!("{} {}", column.name(), if stats.descending { "v" } else { "^" })
+            } else {
+                column.name().to_string()
+            };
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                label,
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::SortStats(*column));
+            }
+        }
+
+        let mut rows = Stack::vertical(table.inset(0, scaled_value(45, scale_y), 0, 0), 0);
+        if stats.games.is_empty() {
+            let row = rows.next(table.width, scaled_value(ROW_HEIGHT, scale_y));
+            ui::text::text(
+                d,
+                row.center_x(),
+                row.center_y(),
+                Color::BLACK,
+                "No games played yet".to_string(),
+                scaled_value(20, scale_y),
+            );
+        }
+
+        for game in stats.games.iter().skip(stats.scroll).take(STATS_ROWS) {
+            let row = rows.next(table.width, scaled_value(ROW_HEIGHT, scale_y));
+            for (column, rect) in HistoryColumn::ALL.iter().zip(columns.iter()) {
+                ui::text::text(
+                    d,
+                    rect.center_x(),
+                    row.center_y(),
+                    Color::BLACK,
+                    cell(game, *column),
+                    scaled_value(20, scale_y),
+                );
+            }
+
+            let confirming = stats.pending_delete == Some(game.id);
+            if ui::button::button(
+                d,
+                ui,
+                delete_column.width,
+                scaled_value(24, scale_y),
+                delete_column.center_x(),
+                row.center_y(),
+                if confirming { Color::PINK } else { Color::WHITE },
+                Color::GRAY,
+                if confirming { "Sure?" } else { "Delete" }.to_string(),
+                scaled_value(18, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                game_manager.save_locked,
+            ) {
+                BUTTON_EVENT.post(Action::DeleteGame(game.id));
+            }
+        }
+
+        // up, the range shown and down, under the table
+        let shown_to = (stats.scroll + STATS_ROWS).min(stats.games.len());
+        let pager = table.anchored(
+            Anchor::Bottom,
+            scaled_value(420, scale_x),
+            scaled_value(30, scale_y),
+            0,
+            0,
+        );
+        let mut pager_row = Stack::horizontal(pager, scaled_value(20, scale_x));
+        let up = pager_row.next(scaled_value(80, scale_x), pager.height);
+        let range = pager_row.next(scaled_value(220, scale_x), pager.height);
+        let down = pager_row.next(scaled_value(80, scale_x), pager.height);
+
+        ui::text::text(
+            d,
+            range.center_x(),
+            range.center_y(),
+            Color::BLACK,
+            format!("{}-{} of {}", (stats.scroll + 1).min(shown_to), shown_to, stats.games.len()),
+            scaled_value(20, scale_y),
+        );
+
+        if ui::button::button(
+            d,
+            ui,
+            up.width,
+            up.height,
+            up.center_x(),
+            up.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Up".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            stats.scroll == 0,
+        ) {
+            BUTTON_EVENT.post(Action::ScrollStatsUp);
+        }
+
+        if ui::button::button(
+            d,
+            ui,
+            down.width,
+            down.height,
+            down.center_x(),
+            down.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Down".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            shown_to >= stats.games.len(),
+        ) {
+            BUTTON_EVENT.post(Action::ScrollStatsDown);
+        }
+
+        // lifetime totals and bests from the save summary
+        let save_data = &game_manager.save_data;
+        let seconds_played = save_data.totals.ticks_played / read_game_statics().tick_rate as i64;
+        let totals = [
+            format!("Games: {}", save_data.totals.games_played),
+            format!("Score: {}", save_data.totals.score),
+            format!("Lines: {}", save_data.totals.lines_cleared),
+            format!("Pieces: {}", save_data.totals.pieces_placed),
+            format!(
+                "Time Played: {}h {:02}m",
+                seconds_played / 3600,
+                seconds_played / 60 % 60
+            ),
+        ];
+        let bests: Vec<String> = save_data
+            .best_games
+            .iter()
+            .map(|best| format!("{}: {}", best.mode.name(), best.score))
+            .collect();
+
+        let mut summary = Stack::vertical(side, 0);
+        for (heading, lines) in [("Lifetime", totals.to_vec()), ("Best Per Mode", bests)] {
+            let rect = summary.next(side.width, scaled_value(ROW_HEIGHT, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::BLACK,
+                heading.to_string(),
+                rect.height,
+            );
+            summary.skip(scaled_value(15, scale_y));
+            for line in lines {
+                let rect = summary.next(side.width, scaled_value(ROW_HEIGHT, scale_y));
+                ui::text::text(
+                    d,
+                    rect.center_x(),
+                    rect.center_y(),
+                    Color::BLACK,
+                    line,
+                    scaled_value(20, scale_y),
+                );
+            }
+            summary.skip(scaled_value(35, scale_y));
+        }
+
+        // the directory to export to, then a button per format
+        let mut exports = Stack::vertical(side.inset(0, scaled_value(440, scale_y), 0, 0), scaled_value(10, scale_y));
+        let field = exports.next(side.width, scaled_value(30, scale_y));
+        let export_dir = ui::text_field::text_field(
+            d,
+            ui,
+            field.width,
+            field.height,
+            field.center_x(),
+            field.center_y(),
+            &stats.export_dir,
+            scaled_value(16, scale_y),
+            EXPORT_DIR_LENGTH,
+        );
+        if export_dir != stats.export_dir {
+            let mut stats = stats.clone();
+            stats.export_dir = export_dir.clone();
+            write_game_manager_stats(stats);
+        }
+
+        let formats = [ExportFormat::Csv, ExportFormat::Json];
+        for format in formats.iter() {
+            let rect = exports.next(scaled_value(150, scale_x), scaled_value(30, scale_y));
+            if ui::button::button(
+                d,
+                ui,
+                rect.width,
+                rect.height,
+                rect.center_x(),
+                rect.center_y(),
+                Color::WHITE,
+                Color::GRAY,
+                format!("Export {}", format.extension().to_uppercase()),
+                scaled_value(20, scale_y),
+                Color::BLACK,
+                Color::BLACK,
+                false,
+            ) {
+                BUTTON_EVENT.post(Action::Export(*format, export_dir.clone()));
+            }
+        }
+
+        if let Some(notice) = &game_manager.notice {
+            let rect = window.anchored(Anchor::Bottom, window.width, scaled_value(20, scale_y), 0, scaled_value(-130, scale_y));
+            ui::text::text(
+                d,
+                rect.center_x(),
+                rect.center_y(),
+                Color::DARKRED,
+                notice.clone(),
+                rect.height,
+            );
+        }
+
+        let back = window.anchored(
+            Anchor::Bottom,
+            scaled_value(115, scale_x),
+            scaled_value(30, scale_y),
+            0,
+            scaled_value(-65, scale_y),
+        );
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
+            Color::WHITE,
+            Color::GRAY,
+            "Back".to_string(),
+            scaled_value(20, scale_y),
+            Color::BLACK,
+            Color::BLACK,
+            false,
+        ) {
+            BUTTON_EVENT.post(Action::Back);
+        }
+    }
+}
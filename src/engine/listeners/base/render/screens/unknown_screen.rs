@@ -1,32 +1,38 @@
-use raylib::{color::Color, prelude::RaylibDraw};
+use raylib::{color::Color, prelude::RaylibDrawHandle};
 
 use crate::engine::{
-    common::ui,
-    lib::RAYLIB_STATE,
-    listeners::base::render::render::{get_scaling_factors, scaled_value}, managers::game_statics::read_game_statics,
+    common::ui::{
+        self,
+        context::UiContext,
+        layout::{Anchor, Rect},
+    },
+    events::{events::BUTTON_EVENT, types::action::Action},
+    listeners::base::render::{render::scaled_value, screen::Screen},
 };
 
-pub fn render_unknown_screen() {
-    let mut state = RAYLIB_STATE.lock().unwrap();
-    if let Some(ref mut raylib_state) = *state {
-        let mut d = raylib_state.rl.begin_drawing(&raylib_state.thread);
-        let (scale_x, scale_y) = get_scaling_factors(&d);
+pub struct UnknownScreen;
 
+impl Screen for UnknownScreen {
+    fn draw(&self, d: &mut RaylibDrawHandle, ui: &mut UiContext, scale_x: f32, scale_y: f32) {
+        let window = Rect::window(d);
+        let title = window.anchored(Anchor::Top, window.width, scaled_value(100, scale_y), 0, scaled_value(66, scale_y));
         ui::text::text(
-            &mut d,
-            scaled_value(800, scale_x),
-            scaled_value(116, scale_y),
+            d,
+            title.center_x(),
+            title.center_y(),
             Color::DARKRED,
             "Screen Not Found".to_string(),
-            scaled_value(100, scale_y),
+            title.height,
         );
 
-        ui::button::button(
-            &mut d,
-            scaled_value(300, scale_x),
-            scaled_value(30, scale_y),
-            scaled_value(800, scale_x),
-            scaled_value(300, scale_y),
+        let back = window.anchored(Anchor::Top, scaled_value(300, scale_x), scaled_value(30, scale_y), 0, scaled_value(285, scale_y));
+        if ui::button::button(
+            d,
+            ui,
+            back.width,
+            back.height,
+            back.center_x(),
+            back.center_y(),
             Color::WHITE,
             Color::GRAY,
             "Return To Main Screen".to_string(),
@@ -34,9 +40,8 @@ pub fn render_unknown_screen() {
             Color::BLACK,
             Color::BLACK,
             false,
-            format!("{}.{}", read_game_statics().url, "button.main_menu".to_string()),
-        );
-
-        d.clear_background(Color::from_hex("cfcefc".as_ref()).unwrap());
+        ) {
+            BUTTON_EVENT.post(Action::MainMenu);
+        }
     }
 }
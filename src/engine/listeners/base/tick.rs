@@ -3,12 +3,17 @@ use std::fmt::Debug;
 use rand::{seq::SliceRandom, Rng};
 
 use crate::engine::{
-    events::events::END_GAME_EVENT,
+    common::{garbage::push_garbage_rows, puzzle::Objective},
+    events::{
+        events::{END_GAME_EVENT, GAMEPLAY_EVENT, START_GAME_EVENT},
+        types::gameplay::Gameplay,
+    },
     managers::{
         game_manager::{
             read_game_manager, write_game_manager_input_buffer, write_game_manager_running,
-            KeyboardAction,
+            write_game_manager_save_data, Block, Control, GameMode, KeyboardAction,
         },
+        game_statics::read_game_statics,
         game_state::{
             read_game_state, write_game_state_all_pieces, write_game_state_arena,
             write_game_state_controlling, write_game_state_current_center,
@@ -16,10 +21,18 @@ use crate::engine::{
             write_game_state_drop_ticks, write_game_state_game_data, write_game_state_ground_ticks,
             write_game_state_has_held, write_game_state_held_piece, write_game_state_left_hold,
             write_game_state_lines_till_next_level, write_game_state_piece_queue,
-            write_game_state_right_hold,
+            write_game_state_right_hold, write_game_state_garbage,
+            write_game_state_garbage_lines_remaining, write_game_state_rise_interval,
+            write_game_state_rise_ticks, write_game_state_last_lock_spin,
+            write_game_state_last_move_rotation, write_game_state_objective_complete,
+            write_game_state_combo, write_game_state_game_over,
+            FIRST_PIECE_ID, GARBAGE_CELL, GHOST_CELL, SPAWN_ROWS,
         },
     },
 };
+
+// how many garbage rows dig mode keeps on screen at once
+pub const DIG_VISIBLE_ROWS: i32 = 10;
 use raylib::prelude::*;
 
 #[derive(PartialEq)] // Add the PartialEq trait
@@ -55,6 +68,9 @@ pub fn on_tick() {
     if !read_game_manager().in_game {
         return;
     }
+    if read_game_state().game_data.mode == GameMode::Puzzle {
+        check_retry();
+    }
 
     if !read_game_manager().running {
         return;
@@ -75,29 +91,103 @@ pub fn on_tick() {
     if read_game_state().game_over {
         return;
     }
+    count_tick();
     check_spawn();
+    if read_game_state().game_over {
+        return;
+    }
     check_move();
     move_down(false);
     destoy_lines();
+    if read_game_state().game_data.mode == GameMode::Survival {
+        check_rise();
+        if read_game_state().game_over {
+            return;
+        }
+    }
     draw_ghost();
     if read_game_manager().input_buffer.len() > 0 {
         write_game_manager_input_buffer(vec![]);
     }
 }
 
+// puzzles can be retried with a single key, even after they've ended
+fn check_retry() {
+    let retry = read_game_manager()
+        .input_buffer
+        .iter()
+        .any(|(key, action)| {
+            *key == read_game_manager().save_data.settings.key_for(Control::Retry)
+                && *action == KeyboardAction::Pressed
+        });
+    if retry {
+        write_game_manager_input_buffer(vec![]);
+        write_game_manager_running(false);
+        START_GAME_EVENT.post(());
+    }
+}
+
+fn count_tick() {
+    let mut game_data = read_game_state().game_data.clone();
+    game_data.ticks_played += 1;
+    write_game_state_game_data(game_data);
+}
+
 fn should_respawn() {
     if read_game_state().ground_ticks > 48 {
         write_game_state_ground_ticks(0);
-        write_game_state_controlling(0);
+        lock_piece();
+    }
+}
+
+fn lock_piece() {
+    write_game_state_last_lock_spin(read_game_state().last_move_rotation && is_immobile());
+    write_game_state_last_move_rotation(false);
+    write_game_state_controlling(0);
+    let mut game_data = read_game_state().game_data.clone();
+    game_data.pieces_placed += 1;
+    write_game_state_game_data(game_data);
+    // a lock that doesn't finish a row breaks the combo
+    if !completes_row() {
+        write_game_state_combo(-1);
     }
+    GAMEPLAY_EVENT.call(Gameplay::PieceLocked {
+        spin: read_game_state().last_lock_spin,
+    });
+}
+
+fn completes_row() -> bool {
+    read_game_state()
+        .arena
+        .iter()
+        .any(|row| row.iter().all(|&cell| cell != 0 && cell != GHOST_CELL))
 }
 
 fn check_spawn() {
     if read_game_state().controlling == 0 {
+        if read_game_state().game_data.mode == GameMode::Dig {
+            refill_dig_garbage();
+        }
+
         let mut rng = read_game_manager().rng.clone();
 
+        if read_game_state().game_data.mode == GameMode::Puzzle
+            && read_game_state().piece_queue.is_empty()
+        {
+            // the held piece is the last one left to play
+            if read_game_state().held_piece.layout.is_empty() {
+                GAMEPLAY_EVENT.call(Gameplay::OutOfPieces);
+                end_game();
+                return;
+            }
+            write_game_state_piece_queue(vec![read_game_state().held_piece.clone()]);
+            write_game_state_held_piece(Block::new());
+        }
+
         // check if piece queue is less than 6
-        if read_game_state().piece_queue.len() < 8 {
+        if read_game_state().piece_queue.len() < 8
+            && read_game_state().game_data.mode != GameMode::Puzzle
+        {
             let shapes = read_game_manager().pieces.clone();
             let mut temp_shpaes = shapes.clone();
             let mut cloned_temp_shpaes = temp_shpaes.clone();
@@ -118,7 +208,7 @@ fn check_spawn() {
         piece_queue.remove(0);
         write_game_state_piece_queue(piece_queue);
 
-        let random = rng.gen::<i32>();
+        let random = rng.gen_range(FIRST_PIECE_ID..=i32::MAX);
 
         let mut arena = read_game_state().arena.clone();
         for (y, row) in shape.layout.iter().enumerate() {
@@ -130,6 +220,9 @@ fn check_spawn() {
 
         write_game_state_current_center((10, 2));
         write_game_state_controlling(random);
+        GAMEPLAY_EVENT.call(Gameplay::PieceSpawned {
+            piece: shape.name.clone(),
+        });
         let mut all_pieces = read_game_state().all_pieces.clone();
         all_pieces.push((random, shape.clone()));
         write_game_state_all_pieces(all_pieces);
@@ -146,7 +239,7 @@ fn check_move() {
             Action::RotateCounterClock => rotate_counter_clock(),
             Action::MoveRight => move_right(),
             Action::MoveLeft => move_left(),
-            Action::MoveDown => _ = move_down(true),
+            Action::MoveDown => soft_drop(),
             Action::Drop => drop(),
             Action::Hold => hold(),
             Action::Pause => {
@@ -192,7 +285,7 @@ fn check_move() {
         down_hold.move_ticks += 1;
         write_game_state_down_hold(down_hold);
         if read_game_state().down_hold.move_ticks > 10 {
-            move_down(true);
+            soft_drop();
             let mut down_hold = read_game_state().down_hold.clone();
             down_hold.move_ticks = 9;
             write_game_state_down_hold(down_hold);
@@ -246,6 +339,8 @@ fn move_right() {
         // game_state.current_center.0 += 1;
         let current_center = read_game_state().current_center.clone();
         write_game_state_current_center((current_center.0 + 1, current_center.1));
+        write_game_state_last_move_rotation(false);
+        GAMEPLAY_EVENT.call(Gameplay::PieceMoved { dx: 1, dy: 0 });
     }
 }
 
@@ -289,6 +384,8 @@ fn move_left() {
         write_game_state_arena(arena);
         let current_center = read_game_state().current_center.clone();
         write_game_state_current_center((current_center.0 - 1, current_center.1));
+        write_game_state_last_move_rotation(false);
+        GAMEPLAY_EVENT.call(Gameplay::PieceMoved { dx: -1, dy: 0 });
     }
 }
 
@@ -339,6 +436,7 @@ fn move_down(forced: bool) -> bool {
         write_game_state_drop_ticks(12.0);
         let current_center = read_game_state().current_center.clone();
         write_game_state_current_center((current_center.0, current_center.1 + 1));
+        write_game_state_last_move_rotation(false);
         return true;
     } else {
         // If it can't move down, update the ground ticks
@@ -349,29 +447,85 @@ fn move_down(forced: bool) -> bool {
     }
 }
 
+fn soft_drop() {
+    if move_down(true) {
+        GAMEPLAY_EVENT.call(Gameplay::PieceMoved { dx: 0, dy: 1 });
+    }
+}
+
 fn drop() {
-    while move_down(true) {}
+    let mut rows = 0;
+    while move_down(true) {
+        rows += 1;
+    }
+    GAMEPLAY_EVENT.call(Gameplay::HardDropped { rows });
     // game_state.controlling = 0;
     // game_state.drop_ticks = 0.0;
-    write_game_state_controlling(0);
+    lock_piece();
     write_game_state_drop_ticks(0.0);
 }
 
+// a piece counts as spun in when it was rotated into a spot it can't slide out of
+fn is_immobile() -> bool {
+    let arena = read_game_state().arena.clone();
+    let controlling = read_game_state().controlling;
+    let blocked = |dx: i32, dy: i32| {
+        for y in 0..arena.len() {
+            for x in 0..arena[y].len() {
+                if arena[y][x] != controlling {
+                    continue;
+                }
+                let pos_x = x as i32 + dx;
+                let pos_y = y as i32 + dy;
+                if pos_x < 0 || pos_y < 0 || pos_x >= arena[y].len() as i32 || pos_y >= arena.len() as i32 {
+                    return true;
+                }
+                let cell = arena[pos_y as usize][pos_x as usize];
+                if cell != 0 && cell != controlling && cell != GHOST_CELL {
+                    return true;
+                }
+            }
+        }
+        false
+    };
+    blocked(-1, 0) && blocked(1, 0) && blocked(0, -1)
+}
+
 fn check_game_over() {
     let arena = read_game_state().arena.clone();
-    for y in 0..5 {
+    for y in 0..SPAWN_ROWS {
         for x in 0..arena[y].len() {
             if arena[y][x] != 0 && arena[y][x] != read_game_state().controlling {
-                END_GAME_EVENT.call();
-                break;
+                if read_game_state().game_data.mode == GameMode::Zen {
+                    clear_board();
+                    return;
+                }
+                GAMEPLAY_EVENT.call(Gameplay::TopOut);
+                end_game();
+                return;
+            }
+        }
+    }
+}
+
+// zen mode can't top out, so the stack is wiped instead
+fn clear_board() {
+    let controlling = read_game_state().controlling;
+    let mut arena = read_game_state().arena.clone();
+    for row in arena.iter_mut() {
+        for cell in row.iter_mut() {
+            if *cell != controlling {
+                *cell = 0;
             }
         }
     }
+    write_game_state_arena(arena);
 }
 
 fn destoy_lines() {
     let mut was_despawned = true;
     let mut despawned = 0;
+    let mut garbage_despawned = 0;
     let mut arena = read_game_state().arena.clone();
     while was_despawned {
         was_despawned = false;
@@ -385,6 +539,9 @@ fn destoy_lines() {
             if full {
                 was_despawned = true;
                 despawned += 1;
+                if arena[y].contains(&GARBAGE_CELL) {
+                    garbage_despawned += 1;
+                }
                 for y2 in (0..y).rev() {
                     for x in 0..arena[y2].len() {
                         arena[y2 + 1][x] = arena[y2][x];
@@ -414,7 +571,12 @@ fn destoy_lines() {
             let mut game_data = read_game_state().game_data.clone();
             game_data.level += 1;
             write_game_state_game_data(game_data);
-            if read_game_state().game_data.level < 13 {
+            GAMEPLAY_EVENT.call(Gameplay::LevelUp {
+                level: read_game_state().game_data.level,
+            });
+            if read_game_state().game_data.level < 13
+                && read_game_state().game_data.mode != GameMode::Zen
+            {
                 // game_state.drop_speed = 1.0 + (game_state.game_data.level as f32 * 0.75) / 2f32;
                 write_game_state_drop_ticks(
                     1.0 + (read_game_state().game_data.level as f32 * 0.75) / 2f32,
@@ -429,7 +591,114 @@ fn destoy_lines() {
         // game_state.game_data.lines_cleared += despawned as i32;
         let mut game_data = read_game_state().game_data.clone();
         game_data.lines_cleared += despawned as i32;
+        game_data.garbage_cleared += garbage_despawned;
         write_game_state_game_data(game_data);
+
+        let combo = read_game_state().combo + 1;
+        write_game_state_combo(combo);
+        GAMEPLAY_EVENT.call(Gameplay::LinesCleared {
+            count: despawned,
+            spin: read_game_state().last_lock_spin,
+            combo,
+        });
+    }
+
+    if read_game_state().game_data.mode == GameMode::Dig && garbage_despawned > 0 {
+        dig_lines_cleared(garbage_despawned);
+    }
+}
+
+// stops the tick straight away, the game is recorded once the frame's events are processed
+fn end_game() {
+    write_game_state_game_over(true);
+    END_GAME_EVENT.post(());
+}
+
+// puzzles check their objective whenever lines are cleared
+pub fn check_objective() {
+    let Some(puzzle) = read_game_manager().puzzle.clone() else {
+        return;
+    };
+    let complete = match puzzle.objective {
+        Objective::ClearLines(lines) => read_game_state().game_data.lines_cleared >= lines,
+        Objective::PerfectClear => {
+            let controlling = read_game_state().controlling;
+            read_game_state()
+                .arena
+                .iter()
+                .flatten()
+                .all(|&cell| cell == 0 || cell == GHOST_CELL || cell == controlling)
+        }
+        Objective::Spin => read_game_state().last_lock_spin,
+    };
+
+    if complete {
+        write_game_state_objective_complete(true);
+        let mut save_data = read_game_manager().save_data.clone();
+        if !save_data.solved_puzzles.contains(&puzzle.id) {
+            save_data.solved_puzzles.push(puzzle.id.clone());
+            write_game_manager_save_data(save_data);
+        }
+        end_game();
+    }
+}
+
+fn dig_lines_cleared(garbage_despawned: i32) {
+    let remaining = read_game_state().garbage_lines_remaining - garbage_despawned;
+    write_game_state_garbage_lines_remaining(remaining);
+    if remaining <= 0 {
+        end_game();
+    }
+}
+
+// tops the pile back up from below while there's more garbage left than shown,
+// done between pieces so nothing is being controlled while the stack moves
+fn refill_dig_garbage() {
+    let remaining = read_game_state().garbage_lines_remaining;
+    let visible = read_game_state()
+        .arena
+        .iter()
+        .filter(|row| row.contains(&GARBAGE_CELL))
+        .count() as i32;
+    let missing = remaining.min(DIG_VISIBLE_ROWS) - visible;
+    if missing > 0 {
+        raise_garbage(missing as usize);
+    }
+}
+
+// pushes garbage in from the bottom, carrying the stack and the active piece with it,
+// returns false if anything was pushed out of the top of the arena
+fn raise_garbage(rows: usize) -> bool {
+    let mut arena = read_game_state().arena.clone();
+    let mut garbage = read_game_state().garbage.clone();
+    let fits = push_garbage_rows(&mut arena, &mut garbage, rows);
+    write_game_state_arena(arena);
+    write_game_state_garbage(garbage);
+
+    let current_center = read_game_state().current_center;
+    write_game_state_current_center((current_center.0, current_center.1.saturating_sub(rows)));
+    fits
+}
+
+fn check_rise() {
+    let rise_ticks = read_game_state().rise_ticks - 1;
+    if rise_ticks > 0 {
+        write_game_state_rise_ticks(rise_ticks);
+        return;
+    }
+
+    // every rise comes a little sooner than the last one
+    let rise_interval = {
+        let statics = read_game_statics();
+        ((read_game_state().rise_interval as f32 * statics.survival_acceleration) as i32)
+            .max(statics.survival_min_interval)
+    };
+    write_game_state_rise_interval(rise_interval);
+    write_game_state_rise_ticks(rise_interval);
+
+    if !raise_garbage(1) {
+        GAMEPLAY_EVENT.call(Gameplay::TopOut);
+        end_game();
     }
 }
 
@@ -503,17 +772,19 @@ fn process_input_buffer() -> Vec<&'static Action> {
     actions
 }
 
+// through the active profile's key bindings
 fn get_action(key: &KeyboardKey) -> Option<Action> {
-    match key {
-        KeyboardKey::KEY_RIGHT => Some(Action::MoveRight),
-        KeyboardKey::KEY_LEFT => Some(Action::MoveLeft),
-        KeyboardKey::KEY_DOWN => Some(Action::MoveDown),
-        KeyboardKey::KEY_SPACE => Some(Action::Drop),
-        KeyboardKey::KEY_UP => Some(Action::RotateClock),
-        KeyboardKey::KEY_Z => Some(Action::RotateCounterClock),
-        KeyboardKey::KEY_LEFT_SHIFT => Some(Action::Hold),
-        KeyboardKey::KEY_ESCAPE => Some(Action::Pause),
-        _ => None,
+    match read_game_manager().save_data.settings.control_for(*key)? {
+        Control::MoveRight => Some(Action::MoveRight),
+        Control::MoveLeft => Some(Action::MoveLeft),
+        Control::MoveDown => Some(Action::MoveDown),
+        Control::Drop => Some(Action::Drop),
+        Control::RotateClock => Some(Action::RotateClock),
+        Control::RotateCounterClock => Some(Action::RotateCounterClock),
+        Control::Hold => Some(Action::Hold),
+        Control::Pause => Some(Action::Pause),
+        // handled by check_retry
+        Control::Retry => None,
     }
 }
 
@@ -592,12 +863,17 @@ fn rotate_clock() {
     }
 
     write_game_state_arena(arena);
+    write_game_state_last_move_rotation(true);
 
     // Update the current piece and center
     // game_state.current_piece.layout = matrix;
     let mut current_piece = read_game_state().current_piece.clone();
     current_piece.layout = matrix;
     write_game_state_current_piece(current_piece);
+    GAMEPLAY_EVENT.call(Gameplay::PieceRotated {
+        clockwise: true,
+        kicked: false,
+    });
 }
 
 fn rotate_counter_clock() {
@@ -675,19 +951,28 @@ fn rotate_counter_clock() {
     }
 
     write_game_state_arena(arena);
+    write_game_state_last_move_rotation(true);
 
     // Update the current piece and center
     let mut current_piece = read_game_state().current_piece.clone();
     current_piece.layout = matrix;
     write_game_state_current_piece(current_piece);
+    GAMEPLAY_EVENT.call(Gameplay::PieceRotated {
+        clockwise: false,
+        kicked: false,
+    });
 }
 
 fn hold() {
+    if !read_game_state().hold_allowed {
+        return;
+    }
     if !read_game_state().has_held {
         write_game_state_has_held(true);
     } else {
         return;
     }
+    GAMEPLAY_EVENT.call(Gameplay::Held);
     let held_piece = read_game_state().held_piece.clone();
     let current_piece = read_game_state().current_piece.clone();
 
@@ -708,7 +993,7 @@ fn hold() {
         write_game_state_held_piece(current_piece.clone());
         write_game_state_controlling(0);
     } else {
-        let random = read_game_manager().rng.clone().gen::<i32>();
+        let random = read_game_manager().rng.clone().gen_range(FIRST_PIECE_ID..=i32::MAX);
 
         // spawn the held piece
         for (y, row) in held_piece.layout.iter().enumerate() {
@@ -737,7 +1022,7 @@ fn clear_ghost() {
     let mut arena = read_game_state().arena.clone();
     for y in 0..arena.len() {
         for x in 0..arena[y].len() {
-            if arena[y][x] == 2 {
+            if arena[y][x] == GHOST_CELL {
                 arena[y][x] = 0;
             }
         }
@@ -756,7 +1041,7 @@ fn draw_ghost() {
     for y in 0..arena.len() {
         for x in 0..arena[y].len() {
             if arena[y][x] == controlling {
-                arena[y][x] = 2;
+                arena[y][x] = GHOST_CELL;
             }
         }
     }
@@ -812,9 +1097,10 @@ fn move_down_ghost() -> bool {
 
 #[cfg(test)]
 mod test {
-    use crate::engine::managers::
-        game_state::{write_game_state, GameState}
-    ;
+    use crate::engine::managers::{
+        game_state::{write_game_state, GameState},
+        lock_globals,
+    };
 
     use super::*;
 
@@ -833,6 +1119,7 @@ mod test {
 
     #[test]
     fn it_does_move_right() {
+        let _globals = lock_globals();
         let game_state = create_game_state();
         write_game_state(game_state);
 
@@ -848,6 +1135,7 @@ mod test {
 
     #[test]
     fn it_does_move_down() {
+        let _globals = lock_globals();
         let game_state = create_game_state();
         write_game_state(game_state);
 
@@ -859,6 +1147,7 @@ mod test {
 
     #[test]
     fn it_does_not_move_down() {
+        let _globals = lock_globals();
         let game_state = create_game_state();
         write_game_state(game_state);
         while move_down(true) {}
@@ -871,6 +1160,7 @@ mod test {
 
     #[test]
     fn it_does_drop() {
+        let _globals = lock_globals();
         let game_state = create_game_state();
         write_game_state(game_state);
 
@@ -886,8 +1176,107 @@ mod test {
     }
 
 
+    #[test]
+    fn it_does_clear_board_on_zen_top_out() {
+        let _globals = lock_globals();
+        let mut game_state = create_game_state();
+        game_state.game_data.mode = GameMode::Zen;
+        game_state.arena[2][5] = 7;
+        game_state.arena[19][5] = 7;
+        write_game_state(game_state);
+
+        check_game_over();
+
+        let game_state = read_game_state();
+        assert!(!game_state.game_over);
+        assert_eq!(game_state.arena[2][5], 0);
+        assert_eq!(game_state.arena[19][5], 0);
+        assert_eq!(game_state.arena[0][3], game_state.controlling);
+        write_game_state(GameState::default());
+    }
+
+    #[test]
+    fn it_does_count_garbage_lines() {
+        let _globals = lock_globals();
+        let mut game_state = create_game_state();
+        game_state.game_data.mode = GameMode::Dig;
+        game_state.garbage_lines_remaining = 3;
+        game_state.arena[19] = vec![GARBAGE_CELL; 10];
+        game_state.arena[19][4] = 9;
+        write_game_state(game_state);
+
+        destoy_lines();
+
+        let game_state = read_game_state();
+        assert_eq!(game_state.game_data.garbage_cleared, 1);
+        assert_eq!(game_state.garbage_lines_remaining, 2);
+        write_game_state(GameState::default());
+    }
+
+    #[test]
+    fn it_does_raise_garbage_with_the_piece() {
+        let _globals = lock_globals();
+        let mut game_state = create_game_state();
+        game_state.arena[0][3] = 0;
+        game_state.arena[1][3] = game_state.controlling;
+        game_state.current_center = (3, 1);
+        write_game_state(game_state);
+
+        assert!(raise_garbage(1));
+
+        let game_state = read_game_state();
+        assert_eq!(game_state.arena[0][3], game_state.controlling);
+        assert_eq!(game_state.current_center, (3, 0));
+        assert!(game_state.arena[19].contains(&GARBAGE_CELL));
+        write_game_state(GameState::default());
+    }
+
+    #[test]
+    fn it_does_detect_a_spin() {
+        let _globals = lock_globals();
+        let mut game_state = create_game_state();
+        game_state.arena[0][3] = 0;
+        game_state.arena[19][3] = game_state.controlling;
+        game_state.arena[19][2] = 7;
+        game_state.arena[19][4] = 7;
+        game_state.arena[18][3] = 7;
+        game_state.last_move_rotation = true;
+        write_game_state(game_state);
+
+        lock_piece();
+
+        assert!(read_game_state().last_lock_spin);
+        write_game_state(GameState::default());
+    }
+
+    #[test]
+    fn it_does_fire_lines_cleared_with_the_combo() {
+        let _globals = lock_globals();
+        let fired = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let recorder = fired.clone();
+        let _handler = GAMEPLAY_EVENT.on_event(move |event| recorder.lock().unwrap().push(event));
+
+        let mut game_state = create_game_state();
+        game_state.arena[0][3] = 0;
+        game_state.arena[19] = vec![7; 10];
+        game_state.arena[19][3] = game_state.controlling;
+        game_state.combo = 0;
+        write_game_state(game_state);
+
+        lock_piece();
+        destoy_lines();
+
+        assert!(fired.lock().unwrap().contains(&Gameplay::LinesCleared {
+            count: 1,
+            spin: false,
+            combo: 1,
+        }));
+        write_game_state(GameState::default());
+    }
+
     #[test]
     fn it_does_move_left() {
+        let _globals = lock_globals();
         let game_state = create_game_state();
         write_game_state(game_state);
 
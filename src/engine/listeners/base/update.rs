@@ -1,32 +1,32 @@
 use std::time::{Duration, Instant};
 
-use raylib::ffi::KeyboardKey;
+use raylib::ffi::{GamepadButton, KeyboardKey};
 
 use crate::engine::{
-    events::events::{RENDER_EVENT, TICK_EVENT},
+    common::ui::context::UI_CONTEXT,
+    events::{
+        events::{BUTTON_EVENT, RENDER_EVENT, TICK_EVENT},
+        types::action::Action,
+    },
     lib::RAYLIB_STATE,
     managers::{
         game_manager::{
             read_game_manager, write_game_manager_delta_time, write_game_manager_input_buffer,
-            write_game_manager_last_update, write_game_manager_tick_accumulator, KeyboardAction,
+            write_game_manager_last_update, write_game_manager_tick_accumulator, Control,
+            KeyboardAction,
         },
         game_statics::read_game_statics,
     },
 };
 
-static USED_KEYS: [KeyboardKey; 8] = [
-    KeyboardKey::KEY_RIGHT,
-    KeyboardKey::KEY_LEFT,
-    KeyboardKey::KEY_DOWN,
-    KeyboardKey::KEY_SPACE,
-    KeyboardKey::KEY_UP,
-    KeyboardKey::KEY_LEFT_SHIFT,
-    KeyboardKey::KEY_ESCAPE,
-    KeyboardKey::KEY_Z,
-];
+// the keys games listen to, as the active profile has them bound
+fn used_keys() -> Vec<KeyboardKey> {
+    let settings = &read_game_manager().save_data.settings;
+    Control::ALL.iter().map(|control| settings.key_for(*control)).collect()
+}
 
 pub fn on_update() {
-    RENDER_EVENT.call();
+    RENDER_EVENT.call(());
     updated_input_buffer();
     do_tick();
 }
@@ -45,7 +45,7 @@ pub fn do_tick() {
             read_game_manager().tick_accumulator - Duration::from_millis(tickrate),
         );
 
-        TICK_EVENT.call();
+        TICK_EVENT.call(());
     }
 }
 
@@ -53,15 +53,24 @@ pub fn updated_input_buffer() {
     {
         let mut state = RAYLIB_STATE.lock().unwrap();
         if let Some(ref mut raylib_state) = *state {
-            for key in USED_KEYS.iter() {
+            for key in used_keys() {
                 let mut input_buffer = read_game_manager().input_buffer.clone();
-                if raylib_state.rl.is_key_pressed(*key) {
-                    input_buffer.push((*key, KeyboardAction::Pressed));
-                } else if raylib_state.rl.is_key_released(*key) {
-                    input_buffer.push((*key, KeyboardAction::Released));
+                if raylib_state.rl.is_key_pressed(key) {
+                    input_buffer.push((key, KeyboardAction::Pressed));
+                } else if raylib_state.rl.is_key_released(key) {
+                    input_buffer.push((key, KeyboardAction::Released));
                 }
                 write_game_manager_input_buffer(input_buffer);
             }
+
+            // in game escape pauses, everywhere else it or the gamepad's right face button goes back a
+            // screen, unless a name is being typed that going back would throw away
+            let back = raylib_state.rl.is_key_pressed(KeyboardKey::KEY_ESCAPE)
+                || (raylib_state.rl.is_gamepad_available(0)
+                    && raylib_state.rl.is_gamepad_button_pressed(0, GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_RIGHT));
+            if !read_game_manager().in_game && back && !UI_CONTEXT.lock().unwrap().was_typing() {
+                BUTTON_EVENT.post(Action::Back);
+            }
         }
     }
 }
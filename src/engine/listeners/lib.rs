@@ -1,120 +1,223 @@
-use event_listener_primitives::HandlerId;
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::{collections::HashMap, sync::Mutex};
 
 use crate::engine::{
-    common::storage,
-    events::events::{
-        BUTTON_EVENT, END_GAME_EVENT, RENDER_EVENT, START_GAME_EVENT, TICK_EVENT, UPDATE_EVENT,
+    audio,
+    common::{
+        navigation,
+        garbage::{push_garbage_rows, GarbageGenerator},
+        leaderboard::{self, LeaderboardEntry},
+        storage::{history, save_file},
+    },
+    events::{
+        events::{
+        BUTTON_EVENT, END_GAME_EVENT, GAMEPLAY_EVENT, RENDER_EVENT, START_GAME_EVENT, TICK_EVENT,
+        UPDATE_EVENT,
+        },
+        types::{action::{handle_action, Action}, event::Subscription, gameplay::Gameplay},
     },
     managers::{
         game_manager::{
-            self, read_game_manager, write_game_manager, write_game_manager_custom_block, write_game_manager_in_game, write_game_manager_running, write_game_manager_save_data, write_game_manager_screen, write_game_manager_should_quit, Block
+            read_game_manager, write_game_manager_in_game, write_game_manager_notice, write_game_manager_pending_entry, write_game_manager_running, write_game_manager_save_data, GameMode
         },
         game_state::{
             read_game_state, write_game_state, write_game_state_game_data,
-            write_game_state_game_over, GameState,
+            write_game_state_game_over, write_game_state_game_recorded, GameState,
         }, game_statics::read_game_statics,
     },
 };
 
-use super::base::{render::render::on_render, tick::on_tick, update::on_update};
+use super::{
+    actions::register_actions,
+    base::{
+        render::render::{on_render, register_screens},
+        tick::{check_objective, on_tick, DIG_VISIBLE_ROWS},
+        update::on_update,
+    },
+};
+
+// handlers that last as long as the game does
+const CORE_LISTENERS: &str = "core";
+// handlers for the game being played, replaced when a new one starts
+pub const GAME_LISTENERS: &str = "game";
+// handlers for the screen being shown, replaced when the screen changes
+pub const SCREEN_LISTENERS: &str = "screen";
+
+// handlers that are removed together, dropping the group unsubscribes all of them,
+// the same way dropping a single Subscription unsubscribes that handler
+#[derive(Default)]
+pub struct ListenerGroup {
+    subscriptions: Vec<Subscription>,
+}
+
+impl ListenerGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, subscription: Subscription) {
+        self.subscriptions.push(subscription);
+    }
+}
 
-static EVENT_HANDLES: Lazy<Mutex<Vec<HandlerId>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static LISTENER_GROUPS: Lazy<Mutex<HashMap<String, ListenerGroup>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 pub fn register_events() {
-    register(UPDATE_EVENT.on_event(on_update));
-    register(TICK_EVENT.on_event(on_tick));
-    register(RENDER_EVENT.on_event(on_render));
-    register(BUTTON_EVENT.on_event(handle_button));
-    register(START_GAME_EVENT.on_event(|| {
+    register_screens();
+    register(UPDATE_EVENT.on_event(|_| on_update()));
+    register(TICK_EVENT.on_event(|_| on_tick()));
+    register(RENDER_EVENT.on_event(|_| on_render()));
+    register_actions();
+    register(BUTTON_EVENT.on_event(|action| {
+        // a mod's button whose mod isn't loaded
+        if !handle_action(&action) {
+            let name = match &action {
+                Action::Custom(name) => name.clone(),
+                action => format!("{:?}", action),
+            };
+            write_game_manager_notice(Some(format!("Unknown action: {}", name)));
+        }
+    }));
+    register(UPDATE_EVENT.on_event(|_| audio::music::on_update()));
+    register(START_GAME_EVENT.on_event(|_| {
+        write_game_manager_pending_entry(None);
         let rand = rand::random::<i32>();
         let mut game_state = GameState::new();
         game_state.game_data.id = rand;
+        game_state.game_data.mode = read_game_manager().mode;
+        if game_state.game_data.mode == GameMode::Dig {
+            let seed = rand::random::<u64>();
+            game_state.game_data.seed = seed;
+            game_state.garbage = GarbageGenerator::new(seed, read_game_statics().dig_messiness);
+            game_state.garbage_lines_remaining = read_game_statics().dig_lines;
+            let rows = game_state.garbage_lines_remaining.min(DIG_VISIBLE_ROWS) as usize;
+            push_garbage_rows(&mut game_state.arena, &mut game_state.garbage, rows);
+        }
+        if game_state.game_data.mode == GameMode::Survival {
+            let seed = rand::random::<u64>();
+            game_state.game_data.seed = seed;
+            game_state.garbage = GarbageGenerator::new(seed, read_game_statics().survival_messiness);
+            game_state.rise_interval = read_game_statics().survival_start_interval;
+            game_state.rise_ticks = game_state.rise_interval;
+        }
+        if game_state.game_data.mode == GameMode::Puzzle {
+            if let Some(puzzle) = read_game_manager().puzzle.clone() {
+                let width = game_state.arena[0].len();
+                let height = game_state.arena.len();
+                game_state.arena = puzzle.build_arena(width, height);
+                // start_puzzle has already turned away a puzzle whose pieces don't resolve
+                game_state.piece_queue =
+                    puzzle.build_queue(&read_game_manager().pieces).unwrap_or_default();
+                game_state.hold_allowed = puzzle.hold;
+            }
+        }
+        register_mode_listeners(game_state.game_data.mode);
         write_game_state(game_state);
         write_game_manager_in_game(true);
         write_game_manager_running(true);
-        write_game_manager_screen("game".to_string());
+        navigation::reset("game");
     }));
 
-    register(END_GAME_EVENT.on_event(|| {
-        // game_state.game_over = true;
+    register(END_GAME_EVENT.on_event(|_| {
+        // quitting from the game over screen ends the game again, it has already been recorded
+        if read_game_state().game_recorded {
+            return;
+        }
+        write_game_state_game_recorded(true);
         write_game_state_game_over(true);
 
         let mut game_data = read_game_state().game_data.clone();
         game_data.end_time = chrono::offset::Utc::now();
-        write_game_state_game_data(game_data);
-
-        // if the score is higher, set this as the best game
-        if read_game_state().game_data.score > read_game_manager().save_data.best_game.score {
-            // game_manager.save_data.best_game = game_state.game_data.clone();
-            let mut save_data = read_game_manager().save_data.clone();
-            save_data.best_game = read_game_state().game_data.clone();
-            write_game_manager_save_data(save_data);
-        }
+        write_game_state_game_data(game_data.clone());
 
-        // check if the id is in save_data.history
+        // update the bests and totals in the summary
         let mut save_data = read_game_manager().save_data.clone();
-        let mut found = false;
-        for game in save_data.history.iter() {
-            if game.id == read_game_state().game_data.id {
-                found = true;
-                break;
-            }
-        }
+        save_data.record(&game_data);
+        write_game_manager_save_data(save_data);
 
-        // if the id is not in save_data.history, add it
-        if !found {
-            save_data.history.push(read_game_state().game_data.clone());
-            write_game_manager_save_data(save_data);
+        // a dig only counts for the leaderboard once all of its garbage is cleared
+        let finished = game_data.mode != GameMode::Dig || read_game_state().garbage_lines_remaining <= 0;
+        let entry = LeaderboardEntry::from_game(read_game_manager().save_data.player_name.clone(), &game_data);
+        let size = read_game_statics().leaderboard_size;
+        if finished && leaderboard::rank(&read_game_manager().save_data.leaderboards, &entry, size).is_some() {
+            write_game_manager_pending_entry(Some(entry));
         }
 
-        // save the game data
-        let serialized_save_data = ron::ser::to_string(&read_game_manager().save_data).unwrap();
-        storage::lib::save("save.rvrs", &serialized_save_data);
+        // append the game to the history and save the summary, unless the save on disk couldn't be read
+        if !read_game_manager().save_locked {
+            let saved = history::append(&game_data)
+                .and_then(|_| save_file::save(&read_game_manager().save_data));
+            if let Err(e) = saved {
+                write_game_manager_notice(Some(format!("Your game could not be saved because {}.", e)));
+            }
+        }
     }));
 }
 
-pub fn register(handler: HandlerId) {
-    EVENT_HANDLES.lock().unwrap().push(handler);
+pub fn register(subscription: Subscription) {
+    register_in(CORE_LISTENERS, subscription);
 }
 
-pub fn handle_button(test: String) {
-    // create a switch statement to handle the different button events
-    match test.as_str().replace(&format!("{}.", &read_game_statics().url), "").as_str() {
-        "button.play" => {
-            START_GAME_EVENT.call();
-        }
-        "button.quit" => {
-            END_GAME_EVENT.call();
-            write_game_manager_should_quit(true);
-        }
-        "button.restart" => {
-            write_game_manager_running(false);
-            START_GAME_EVENT.call();
-        }
-        "button.resume" => {
-            write_game_manager_running(true);
-        }
-        "button.main_menu" => {
-            END_GAME_EVENT.call();
-            write_game_manager_running(false);
-            write_game_manager_in_game(false);
-            write_game_manager_screen("main".to_string());
-        }
-        "button.blocks" => {
-            write_game_manager_custom_block(Block::default());
-            write_game_manager_screen("create_shape".to_string());
-        }
-        "button.save_shape" => {
-            let mut game_manager = game_manager::read_game_manager_only();
-            game_manager.pieces.push(game_manager.custom_block.clone());
-            write_game_manager_custom_block(Block::default());
-            write_game_manager(game_manager);
-            BUTTON_EVENT.call(format!("{}.{}", read_game_statics().url, "button.main_menu".to_string()));
-        }
-        _ => {
-            println!("Unknown: {}", test);
+pub fn register_in(group: &str, subscription: Subscription) {
+    LISTENER_GROUPS
+        .lock()
+        .unwrap()
+        .entry(group.to_string())
+        .or_default()
+        .add(subscription);
+}
+
+pub fn unregister_group(group: &str) {
+    // bound so the handlers are dropped after the lock is released
+    let _removed = LISTENER_GROUPS.lock().unwrap().remove(group);
+}
+
+// swaps a whole group for another, unsubscribing everything the old one held
+pub fn replace_group(group: &str, listeners: ListenerGroup) {
+    let _replaced = LISTENER_GROUPS.lock().unwrap().insert(group.to_string(), listeners);
+}
+
+// handlers only the mode being started needs
+fn register_mode_listeners(mode: GameMode) {
+    unregister_group(GAME_LISTENERS);
+    if mode == GameMode::Puzzle {
+        register_in(
+            GAME_LISTENERS,
+            GAMEPLAY_EVENT.on_event(|event| {
+                if let Gameplay::LinesCleared { .. } = event {
+                    check_objective();
+                }
+            }),
+        );
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::events::types::event::Event;
+    use std::sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn it_does_unsubscribe_a_whole_group() {
+        let event: Event<i32> = Event::new();
+        let total = Arc::new(AtomicI32::new(0));
+        for _ in 0..2 {
+            let total = total.clone();
+            register_in("test", event.on_event(move |arg| {
+                total.fetch_add(arg, Ordering::Relaxed);
+            }));
         }
+
+        event.call(1);
+        unregister_group("test");
+        event.call(1);
+
+        assert_eq!(total.load(Ordering::Relaxed), 2);
     }
 }
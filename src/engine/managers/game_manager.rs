@@ -2,8 +2,9 @@ use arc_swap::ArcSwap;
 use chrono::{DateTime, Utc};
 use lazy_static::lazy_static;
 
-use raylib::{color::Color, ffi::KeyboardKey};
+use raylib::{color::Color, core::input::key_from_i32, ffi::KeyboardKey};
 use std::{
+    collections::BTreeMap,
     fmt::Debug,
     sync::Arc,
     time::{Duration, Instant},
@@ -11,6 +12,15 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
+use crate::engine::common::{
+    leaderboard::LeaderboardEntry,
+    navigation::ScreenResult,
+    puzzle::{Puzzle, PuzzleBlock},
+    storage::{
+        history::HistoryColumn, migrations::CURRENT_SAVE_VERSION, profile::ProfileIndex,
+    },
+};
+
 #[derive(PartialEq)] // Add the PartialEq trait
 pub enum KeyboardAction {
     Pressed,
@@ -43,33 +53,262 @@ impl Default for Block {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum GameMode {
+    #[default]
+    Marathon,
+    Zen,
+    Dig,
+    Survival,
+    Puzzle,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Marathon => "Marathon",
+            GameMode::Zen => "Zen",
+            GameMode::Dig => "Dig",
+            GameMode::Survival => "Survival",
+            GameMode::Puzzle => "Puzzle",
+        }
+    }
+}
+
+// the small summary file, finished games themselves live in the history log
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SaveData {
+    #[serde(default)]
+    pub version: u32,
     pub check_sum: i32,
     pub best_game: GameData,
-    pub history: Vec<GameData>,
+    // the best game of each mode that has been played
+    #[serde(default)]
+    pub best_games: Vec<GameData>,
+    #[serde(default)]
+    pub totals: HistoryTotals,
+    #[serde(default)]
+    pub solved_puzzles: Vec<String>,
+    #[serde(default)]
+    pub leaderboards: Vec<LeaderboardEntry>,
+    // offered again the next time a run makes a leaderboard
+    #[serde(default)]
+    pub player_name: String,
+    // blocks made in the block editor, added to the default pieces when the profile is opened
+    #[serde(default)]
+    pub custom_blocks: Vec<PuzzleBlock>,
+    #[serde(default)]
+    pub settings: Settings,
 }
 
 impl SaveData {
     pub fn new() -> Self {
         Self {
+            version: CURRENT_SAVE_VERSION,
             check_sum: 0,
             best_game: GameData::new(),
-            history: vec![],
+            best_games: vec![],
+            totals: HistoryTotals::default(),
+            solved_puzzles: vec![],
+            leaderboards: vec![],
+            player_name: "".to_string(),
+            custom_blocks: vec![],
+            settings: Settings::default(),
         }
     }
+
+    // folds a finished game into the bests and totals, puzzles are kept out of both
+    // the same way they are kept off the leaderboards
+    pub fn record(&mut self, game: &GameData) {
+        if game.mode == GameMode::Puzzle {
+            return;
+        }
+        if game.score > self.best_game.score {
+            self.best_game = game.clone();
+        }
+        match self.best_games.iter_mut().find(|best| best.mode == game.mode) {
+            Some(best) => {
+                if game.score > best.score {
+                    *best = game.clone();
+                }
+            }
+            None => self.best_games.push(game.clone()),
+        }
+        self.totals.add(game);
+    }
 }
 
 impl Clone for SaveData {
     fn clone(&self) -> Self {
         Self {
+            version: self.version,
             check_sum: self.check_sum,
             best_game: self.best_game.clone(),
-            history: self.history.clone(),
+            best_games: self.best_games.clone(),
+            totals: self.totals.clone(),
+            solved_puzzles: self.solved_puzzles.clone(),
+            leaderboards: self.leaderboards.clone(),
+            player_name: self.player_name.clone(),
+            custom_blocks: self.custom_blocks.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+// what the music does as the level goes up
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MusicIntensity {
+    #[default]
+    Off,
+    // moves along the playlist every few levels
+    Track,
+    // plays the same track a little faster each level
+    Tempo,
+}
+
+impl MusicIntensity {
+    pub const ALL: [MusicIntensity; 3] =
+        [MusicIntensity::Off, MusicIntensity::Track, MusicIntensity::Tempo];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MusicIntensity::Off => "Off",
+            MusicIntensity::Track => "Change track",
+            MusicIntensity::Tempo => "Speed up",
+        }
+    }
+}
+
+// what a key does in a game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Control {
+    MoveRight,
+    MoveLeft,
+    MoveDown,
+    Drop,
+    RotateClock,
+    RotateCounterClock,
+    Hold,
+    Pause,
+    // puzzles only
+    Retry,
+}
+
+impl Control {
+    pub const ALL: [Control; 9] = [
+        Control::MoveRight,
+        Control::MoveLeft,
+        Control::MoveDown,
+        Control::Drop,
+        Control::RotateClock,
+        Control::RotateCounterClock,
+        Control::Hold,
+        Control::Pause,
+        Control::Retry,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Control::MoveRight => "Move right",
+            Control::MoveLeft => "Move left",
+            Control::MoveDown => "Move down",
+            Control::Drop => "Drop",
+            Control::RotateClock => "Rotate",
+            Control::RotateCounterClock => "Rotate back",
+            Control::Hold => "Hold",
+            Control::Pause => "Pause",
+            Control::Retry => "Retry puzzle",
+        }
+    }
+
+    pub fn default_key(&self) -> KeyboardKey {
+        match self {
+            Control::MoveRight => KeyboardKey::KEY_RIGHT,
+            Control::MoveLeft => KeyboardKey::KEY_LEFT,
+            Control::MoveDown => KeyboardKey::KEY_DOWN,
+            Control::Drop => KeyboardKey::KEY_SPACE,
+            Control::RotateClock => KeyboardKey::KEY_UP,
+            Control::RotateCounterClock => KeyboardKey::KEY_Z,
+            Control::Hold => KeyboardKey::KEY_LEFT_SHIFT,
+            Control::Pause => KeyboardKey::KEY_ESCAPE,
+            Control::Retry => KeyboardKey::KEY_R,
+        }
+    }
+}
+
+// how a key is shown to the player, KEY_LEFT_SHIFT as Left Shift
+pub fn key_name(key: KeyboardKey) -> String {
+    format!("{:?}", key)
+        .trim_start_matches("KEY_")
+        .split('_')
+        .map(|word| word[..1].to_string() + &word[1..].to_lowercase())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// volumes go from 0 to 1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    pub music_intensity: MusicIntensity,
+    // raylib key codes, a control that isn't in here uses its default key
+    pub key_bindings: BTreeMap<Control, i32>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            music_volume: 0.5,
+            effects_volume: 0.3,
+            music_intensity: MusicIntensity::Off,
+            key_bindings: BTreeMap::new(),
         }
     }
 }
 
+impl Settings {
+    pub fn key_for(&self, control: Control) -> KeyboardKey {
+        self.key_bindings
+            .get(&control)
+            .and_then(|code| key_from_i32(*code))
+            .unwrap_or(control.default_key())
+    }
+
+    pub fn control_for(&self, key: KeyboardKey) -> Option<Control> {
+        Control::ALL.into_iter().find(|control| self.key_for(*control) == key)
+    }
+
+    // a key already bound to another control is swapped with this one's, so no key does two things
+    pub fn bind(&mut self, control: Control, key: KeyboardKey) {
+        let previous = self.key_for(control);
+        if let Some(other) = self.control_for(key).filter(|other| *other != control) {
+            self.key_bindings.insert(other, previous as i32);
+        }
+        self.key_bindings.insert(control, key as i32);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HistoryTotals {
+    pub games_played: i64,
+    pub score: i64,
+    pub lines_cleared: i64,
+    pub pieces_placed: i64,
+    pub ticks_played: i64,
+}
+
+impl HistoryTotals {
+    pub fn add(&mut self, game: &GameData) {
+        self.games_played += 1;
+        self.score += game.score as i64;
+        self.lines_cleared += game.lines_cleared as i64;
+        self.pieces_placed += game.pieces_placed as i64;
+        self.ticks_played += game.ticks_played as i64;
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GameData {
     pub id: i32,
@@ -78,6 +317,16 @@ pub struct GameData {
     pub lines_cleared: i32,
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub pieces_placed: i32,
+    #[serde(default)]
+    pub ticks_played: i32,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub garbage_cleared: i32,
 }
 
 impl GameData {
@@ -89,6 +338,11 @@ impl GameData {
             lines_cleared: 0,
             start_time: Utc::now(),
             end_time: Utc::now(),
+            mode: GameMode::default(),
+            pieces_placed: 0,
+            ticks_played: 0,
+            seed: 0,
+            garbage_cleared: 0,
         }
     }
 }
@@ -102,6 +356,11 @@ impl Clone for GameData {
             lines_cleared: self.lines_cleared,
             start_time: self.start_time,
             end_time: self.end_time,
+            mode: self.mode,
+            pieces_placed: self.pieces_placed,
+            ticks_played: self.ticks_played,
+            seed: self.seed,
+            garbage_cleared: self.garbage_cleared,
         }
     }
 }
@@ -136,6 +395,191 @@ impl Clone for KeyboardAction {
     }
 }
 
+// what the stats screen is showing
+#[derive(Debug, Clone)]
+pub struct StatsView {
+    pub games: Vec<GameData>,
+    pub column: HistoryColumn,
+    pub descending: bool,
+    // index of the first visible row
+    pub scroll: usize,
+    // a delete has to be pressed twice, this is the game waiting on the second press
+    pub pending_delete: Option<i32>,
+    // where exports are written, as typed on the screen
+    pub export_dir: String,
+}
+
+impl StatsView {
+    pub fn new() -> Self {
+        Self {
+            games: vec![],
+            column: HistoryColumn::Date,
+            descending: true,
+            scroll: 0,
+            pending_delete: None,
+            export_dir: String::new(),
+        }
+    }
+}
+
+impl Default for StatsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a profile being created, or renamed when it has an id
+#[derive(Debug, Clone)]
+pub struct ProfileEdit {
+    pub id: Option<String>,
+    pub name: String,
+}
+
+// what the profile picker is showing
+#[derive(Debug, Clone, Default)]
+pub struct ProfilesView {
+    pub index: ProfileIndex,
+    pub editing: Option<ProfileEdit>,
+    // a delete has to be pressed twice, this is the profile waiting on the second press
+    pub pending_delete: Option<String>,
+}
+
+// the built in pieces, custom blocks from the profile get added after these
+pub fn default_pieces() -> Vec<Block> {
+    vec![
+        Block {
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 1, 1, 0, 0],
+                vec![0, 1, 1, 0, 0],
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: false,
+            color: Color::FIREBRICK,
+            name: "Small Block".to_string(),
+            active: true,
+        },
+        Block {
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: false,
+            color: Color::RED,
+            name: "Medium Block".to_string(),
+            active: true,
+        },
+        Block {
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::BLUE,
+            name: "Small T".to_string(),
+            active: true,
+        },
+        Block {
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::DARKBLUE,
+            name: "BIG T".to_string(),
+            active: true,
+        },
+        Block {
+            layout: vec![
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 1, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::GREEN,
+            name: "L".to_string(),
+            active: true,
+        },
+        Block {
+            name: "J".to_string(),
+            layout: vec![
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 1, 1, 0, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::DARKGREEN,
+            active: true,
+        },
+        Block {
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 1, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::GREEN,
+            name: "Small L".to_string(),
+            active: true,
+        },
+        Block {
+            name: "Small J".to_string(),
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 1, 1, 0, 0],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::DARKGREEN,
+            active: true,
+        },
+        Block {
+            name: "I".to_string(),
+            layout: vec![
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 0, 1, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::DARKGREEN,
+            active: true,
+        },
+        Block {
+            name: "Pyramid".to_string(),
+            layout: vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 0, 1, 0, 0],
+                vec![0, 1, 1, 1, 0],
+                vec![1, 1, 1, 1, 1],
+                vec![0, 0, 0, 0, 0],
+            ],
+            can_rotate: true,
+            color: Color::PURPLE,
+            active: true,
+        },
+    ]
+}
+
 pub struct GameManager {
     pub rng: rand::rngs::ThreadRng,
     pub last_update: Instant,
@@ -156,9 +600,34 @@ pub struct GameManager {
     pub save_data: SaveData,
 
     pub screen: String,
+    // the screens under the current one, the last one is where going back leads
     pub screen_path: Vec<String>,
+    // handed from a screen that was closed to the one under it
+    pub screen_result: Option<ScreenResult>,
 
     pub custom_block: Block,
+
+    pub mode: GameMode,
+
+    pub puzzles: Vec<Puzzle>,
+    pub puzzle: Option<Puzzle>,
+
+    // shown on the main menu until dismissed
+    pub notice: Option<String>,
+    // set when the save file couldn't be used, so it never gets written over
+    pub save_locked: bool,
+    // the active profile's save backups, newest first, listed when the settings screen opens
+    pub backups: Vec<String>,
+
+    pub stats: StatsView,
+
+    // a finished run that made a leaderboard, waiting on a name
+    pub pending_entry: Option<LeaderboardEntry>,
+    pub leaderboard_mode: GameMode,
+
+    pub profiles: ProfilesView,
+    // the control the rebind dialog is choosing a key for
+    pub rebinding: Option<Control>,
 }
 
 impl GameManager {
@@ -166,6 +635,7 @@ impl GameManager {
         Self {
             screen: "main".to_string(),
             screen_path: vec![],
+            screen_result: None,
             rng: rand::thread_rng(),
             last_update: Instant::now(),
             tick_accumulator: Duration::from_secs(0),
@@ -174,141 +644,21 @@ impl GameManager {
             running: false,
             should_quit: false,
             input_buffer: vec![],
-            pieces: vec![
-                Block {
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 1, 1, 0, 0],
-                        vec![0, 1, 1, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: false,
-                    color: Color::FIREBRICK,
-                    name: "Small Block".to_string(),
-                    active: true,
-                },
-                Block {
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: false,
-                    color: Color::RED,
-                    name: "Medium Block".to_string(),
-                    active: true,
-                },
-                Block {
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::BLUE,
-                    name: "Small T".to_string(),
-                    active: true,
-                },
-                Block {
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::DARKBLUE,
-                    name: "BIG T".to_string(),
-                    active: true,
-                },
-                Block {
-                    layout: vec![
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 1, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::GREEN,
-                    name: "L".to_string(),
-                    active: true,
-                },
-                Block {
-                    name: "J".to_string(),
-                    layout: vec![
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 1, 1, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::DARKGREEN,
-                    active: true,
-                },
-                Block {
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 1, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::GREEN,
-                    name: "Small L".to_string(),
-                    active: true,
-                },
-                Block {
-                    name: "Small J".to_string(),
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 1, 1, 0, 0],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::DARKGREEN,
-                    active: true,
-                },
-                Block {
-                    name: "I".to_string(),
-                    layout: vec![
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::DARKGREEN,
-                    active: true,
-                },
-                Block {
-                    name: "Pyramid".to_string(),
-                    layout: vec![
-                        vec![0, 0, 0, 0, 0],
-                        vec![0, 0, 1, 0, 0],
-                        vec![0, 1, 1, 1, 0],
-                        vec![1, 1, 1, 1, 1],
-                        vec![0, 0, 0, 0, 0],
-                    ],
-                    can_rotate: true,
-                    color: Color::PURPLE,
-                    active: true,
-                },
-            ],
+            pieces: default_pieces(),
             app_start_time: Instant::now(),
             save_data: SaveData::new(),
             custom_block: Block::new(),
+            mode: GameMode::default(),
+            puzzles: vec![],
+            puzzle: None,
+            notice: None,
+            save_locked: false,
+            backups: vec![],
+            stats: StatsView::new(),
+            pending_entry: None,
+            leaderboard_mode: GameMode::default(),
+            profiles: ProfilesView::default(),
+            rebinding: None,
         }
     }
 }
@@ -321,6 +671,7 @@ impl Clone for GameManager {
         GameManager {
             screen: self.screen.clone(),
             screen_path: self.screen_path.clone(),
+            screen_result: self.screen_result.clone(),
             rng: rand::thread_rng(), // The RNG can't be cloned directly; reinitialize
             last_update: self.last_update,
             tick_accumulator: self.tick_accumulator,
@@ -333,6 +684,17 @@ impl Clone for GameManager {
             app_start_time: self.app_start_time,
             save_data: self.save_data.clone(),
             custom_block: self.custom_block.clone(),
+            mode: self.mode,
+            puzzles: self.puzzles.clone(),
+            puzzle: self.puzzle.clone(),
+            notice: self.notice.clone(),
+            save_locked: self.save_locked,
+            backups: self.backups.clone(),
+            stats: self.stats.clone(),
+            pending_entry: self.pending_entry.clone(),
+            leaderboard_mode: self.leaderboard_mode,
+            profiles: self.profiles.clone(),
+            rebinding: self.rebinding,
         }
     }
 }
@@ -430,4 +792,106 @@ pub fn write_game_manager_custom_block(custom_blocks: Block) {
     let mut game_manager = read_game_manager_only();
     game_manager.custom_block = custom_blocks;
     GAME_MANAGER.store(Arc::new(game_manager));
-}
\ No newline at end of file
+}
+
+pub fn write_game_manager_mode(mode: GameMode) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.mode = mode;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_puzzles(puzzles: Vec<Puzzle>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.puzzles = puzzles;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_puzzle(puzzle: Option<Puzzle>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.puzzle = puzzle;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_notice(notice: Option<String>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.notice = notice;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_save_locked(save_locked: bool) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.save_locked = save_locked;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_backups(backups: Vec<String>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.backups = backups;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_rebinding(rebinding: Option<Control>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.rebinding = rebinding;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_stats(stats: StatsView) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.stats = stats;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_pending_entry(pending_entry: Option<LeaderboardEntry>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.pending_entry = pending_entry;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_leaderboard_mode(leaderboard_mode: GameMode) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.leaderboard_mode = leaderboard_mode;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_profiles(profiles: ProfilesView) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.profiles = profiles;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+pub fn write_game_manager_screen_result(screen_result: Option<ScreenResult>) {
+    let mut game_manager = read_game_manager_only();
+    game_manager.screen_result = screen_result;
+    GAME_MANAGER.store(Arc::new(game_manager));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_does_swap_a_key_bound_twice() {
+        let mut settings = Settings::default();
+        settings.bind(Control::Drop, KeyboardKey::KEY_UP);
+
+        assert_eq!(settings.key_for(Control::Drop), KeyboardKey::KEY_UP);
+        assert_eq!(settings.key_for(Control::RotateClock), KeyboardKey::KEY_SPACE);
+        assert_eq!(settings.control_for(KeyboardKey::KEY_UP), Some(Control::Drop));
+        assert_eq!(settings.key_for(Control::Hold), KeyboardKey::KEY_LEFT_SHIFT);
+        assert_eq!(key_name(KeyboardKey::KEY_LEFT_SHIFT), "Left Shift");
+    }
+
+    #[test]
+    fn it_does_not_record_puzzles() {
+        let mut save_data = SaveData::new();
+        let mut game = GameData::new();
+        game.mode = GameMode::Puzzle;
+        game.score = 500;
+        save_data.record(&game);
+
+        assert_eq!(save_data.best_game.score, 0);
+        assert!(save_data.best_games.is_empty());
+        assert_eq!(save_data.totals, HistoryTotals::default());
+    }
+}
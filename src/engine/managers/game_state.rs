@@ -1,9 +1,20 @@
 use super::game_manager::{Block, GameData};
+use crate::engine::common::garbage::GarbageGenerator;
 use arc_swap::ArcSwap;
 use lazy_static::lazy_static;
 use raylib::color::Color;
 use std::{fmt::Debug, sync::Arc};
 
+pub const GHOST_CELL: i32 = 2;
+pub const GARBAGE_CELL: i32 = 3;
+// pieces are marked in the arena by a random id, kept above the ghost and garbage values so a
+// piece is never drawn or cleared as one of them
+pub const FIRST_PIECE_ID: i32 = GARBAGE_CELL + 1;
+pub const ARENA_WIDTH: usize = 21;
+pub const ARENA_HEIGHT: usize = 41;
+// the rows pieces spawn in, anything left there tops out
+pub const SPAWN_ROWS: usize = 5;
+
 #[derive(Debug)]
 pub struct GameState {
     pub controlling: i32,
@@ -16,6 +27,8 @@ pub struct GameState {
     pub has_held: bool,
 
     pub game_over: bool,
+    // set by the end of game listener so a game is only recorded once
+    pub game_recorded: bool,
     pub game_data: GameData,
     pub lines_till_next_level: i32,
 
@@ -28,6 +41,20 @@ pub struct GameState {
     pub down_hold: ActionManager,
 
     pub piece_queue: Vec<Block>,
+
+    pub garbage: GarbageGenerator,
+    pub garbage_lines_remaining: i32,
+
+    pub rise_ticks: i32,
+    pub rise_interval: i32,
+
+    pub hold_allowed: bool,
+    pub objective_complete: bool,
+    // whether the last thing that moved the piece was a rotation
+    pub last_move_rotation: bool,
+    pub last_lock_spin: bool,
+    // line clears in a row without a lock that cleared nothing, -1 when there's no combo going
+    pub combo: i32,
 }
 
 impl Default for GameState {
@@ -44,7 +71,7 @@ pub struct ActionManager {
 impl GameState {
     pub fn new() -> Self {
         Self {
-            arena: vec![vec![0; 21]; 41],
+            arena: vec![vec![0; ARENA_WIDTH]; ARENA_HEIGHT],
             controlling: 0,
             drop_speed: 1.0,
             drop_ticks: 0.0,
@@ -54,6 +81,7 @@ impl GameState {
 
             lines_till_next_level: 6,
             game_over: false,
+            game_recorded: false,
 
             left_hold: ActionManager {
                 is_pressed: false,
@@ -88,6 +116,15 @@ impl GameState {
             has_held: false,
             all_pieces: vec![],
             piece_queue: vec![],
+            garbage: GarbageGenerator::default(),
+            garbage_lines_remaining: 0,
+            rise_ticks: 0,
+            rise_interval: 0,
+            hold_allowed: true,
+            objective_complete: false,
+            last_move_rotation: false,
+            last_lock_spin: false,
+            combo: -1,
         }
     }
 }
@@ -106,6 +143,7 @@ impl Clone for GameState {
             game_data: self.game_data.clone(),
             lines_till_next_level: self.lines_till_next_level,
             game_over: self.game_over,
+            game_recorded: self.game_recorded,
             left_hold: self.left_hold.clone(),
             right_hold: self.right_hold.clone(),
             down_hold: self.down_hold.clone(),
@@ -115,6 +153,15 @@ impl Clone for GameState {
             has_held: self.has_held,
             all_pieces: self.all_pieces.clone(),
             piece_queue: self.piece_queue.clone(),
+            garbage: self.garbage.clone(),
+            garbage_lines_remaining: self.garbage_lines_remaining,
+            rise_ticks: self.rise_ticks,
+            rise_interval: self.rise_interval,
+            hold_allowed: self.hold_allowed,
+            objective_complete: self.objective_complete,
+            last_move_rotation: self.last_move_rotation,
+            last_lock_spin: self.last_lock_spin,
+            combo: self.combo,
         }
     }
 }
@@ -192,6 +239,12 @@ pub fn write_game_state_game_over(game_over: bool) {
     write_game_state(game_manager);
 }
 
+pub fn write_game_state_game_recorded(game_recorded: bool) {
+    let mut game_manager = read_game_state_only();
+    game_manager.game_recorded = game_recorded;
+    write_game_state(game_manager);
+}
+
 pub fn write_game_state_left_hold(left_hold: ActionManager) {
     let mut game_manager = read_game_state_only();
     game_manager.left_hold = left_hold;
@@ -245,3 +298,51 @@ pub fn write_game_state_piece_queue(piece_queue: Vec<Block>) {
     game_manager.piece_queue = piece_queue;
     write_game_state(game_manager);
 }
+
+pub fn write_game_state_garbage(garbage: GarbageGenerator) {
+    let mut game_manager = read_game_state_only();
+    game_manager.garbage = garbage;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_garbage_lines_remaining(garbage_lines_remaining: i32) {
+    let mut game_manager = read_game_state_only();
+    game_manager.garbage_lines_remaining = garbage_lines_remaining;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_rise_ticks(rise_ticks: i32) {
+    let mut game_manager = read_game_state_only();
+    game_manager.rise_ticks = rise_ticks;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_rise_interval(rise_interval: i32) {
+    let mut game_manager = read_game_state_only();
+    game_manager.rise_interval = rise_interval;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_objective_complete(objective_complete: bool) {
+    let mut game_manager = read_game_state_only();
+    game_manager.objective_complete = objective_complete;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_last_move_rotation(last_move_rotation: bool) {
+    let mut game_manager = read_game_state_only();
+    game_manager.last_move_rotation = last_move_rotation;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_last_lock_spin(last_lock_spin: bool) {
+    let mut game_manager = read_game_state_only();
+    game_manager.last_lock_spin = last_lock_spin;
+    write_game_state(game_manager);
+}
+
+pub fn write_game_state_combo(combo: i32) {
+    let mut game_manager = read_game_state_only();
+    game_manager.combo = combo;
+    write_game_state(game_manager);
+}
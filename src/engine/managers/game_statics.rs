@@ -5,6 +5,23 @@ pub struct GameStatics {
     pub tick_rate: i32,
     pub block_size: i32,
     pub url: String,
+
+    pub dig_lines: i32,
+    pub dig_messiness: f32,
+
+    // survival intervals are in ticks
+    pub survival_start_interval: i32,
+    pub survival_min_interval: i32,
+    pub survival_acceleration: f32,
+    pub survival_warning_ticks: i32,
+    pub survival_messiness: f32,
+
+    // how many timestamped copies of the save are kept in backups/
+    pub backup_count: usize,
+
+    // entries kept on each mode's leaderboard
+    pub leaderboard_size: usize,
+    pub player_name_length: usize,
 }
 
 impl GameStatics {
@@ -13,6 +30,20 @@ impl GameStatics {
             tick_rate: 60,
             block_size: 16,
             url: "com.catsonluna.revris".to_string(),
+
+            dig_lines: 18,
+            dig_messiness: 0.25,
+
+            survival_start_interval: 600,
+            survival_min_interval: 90,
+            survival_acceleration: 0.92,
+            survival_warning_ticks: 90,
+            survival_messiness: 0.6,
+
+            backup_count: 5,
+
+            leaderboard_size: 10,
+            player_name_length: 12,
         }
     }
 }
@@ -1,3 +1,15 @@
 pub mod game_manager;
 pub mod game_state;
 pub mod game_statics;
+
+#[cfg(test)]
+use std::sync::{Mutex, MutexGuard};
+
+// tests that write GAME_STATE, GAME_MANAGER or the action handlers hold this while they run,
+// so tests on other threads can't change the state under them
+#[cfg(test)]
+pub fn lock_globals() -> MutexGuard<'static, ()> {
+    static GLOBALS: Mutex<()> = Mutex::new(());
+    // a failed test poisons the lock, which shouldn't fail the ones after it
+    GLOBALS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -1,5 +1,7 @@
 pub mod lib;
 
+pub mod audio;
+pub mod cli;
 pub mod common;
 pub mod events;
 pub mod listeners;
@@ -1,4 +1,8 @@
 pub mod engine;
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = engine::cli::run(&args) {
+        std::process::exit(code);
+    }
     engine::lib::start();
 }